@@ -0,0 +1,197 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Lock-free access to shared values based on [`arc_swap::ArcSwap`].
+
+use std::sync::Arc;
+
+#[cfg(feature = "tokio-rt")]
+use std::{
+    future::{poll_fn, Future as _},
+    pin::{pin, Pin},
+    task::Poll,
+};
+
+use arc_swap::ArcSwap;
+
+use crate::{Publisher, Subscriber};
+
+#[cfg(feature = "tokio-rt")]
+impl<T> Publisher<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Track the latest value for lock-free access.
+    ///
+    /// Spawns a background task that updates the returned [`LatestArc`]
+    /// on every change. The task finishes after either the publisher
+    /// or the returned [`LatestArc`] and all its clones have been dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not called from within a Tokio runtime.
+    #[must_use]
+    pub fn into_latest_arc(&self) -> LatestArc<T> {
+        let mut subscriber = self.subscribe();
+        let (dropped_tx, mut dropped_rx) = tokio::sync::oneshot::channel();
+        let inner = Arc::new(LatestArcInner {
+            latest: ArcSwap::from_pointee(subscriber.read_ack().clone()),
+            _dropped_tx: dropped_tx,
+        });
+        let inner_weak = Arc::downgrade(&inner);
+        tokio::spawn(async move {
+            loop {
+                let mut next_changed = pin!(subscriber.map_changed(|next| Arc::new(next.clone())));
+                let next_changed = poll_fn(|cx| {
+                    if Pin::new(&mut dropped_rx).poll(cx).is_ready() {
+                        return Poll::Ready(None);
+                    }
+                    next_changed.as_mut().poll(cx).map(Some)
+                })
+                .await;
+                let Some(next_changed) = next_changed else {
+                    // All instances of LatestArc have been dropped.
+                    break;
+                };
+                let Ok(next_value) = next_changed else {
+                    // Publisher has disappeared.
+                    break;
+                };
+                let Some(inner) = inner_weak.upgrade() else {
+                    // All instances of LatestArc have been dropped.
+                    break;
+                };
+                inner.latest.store(next_value);
+            }
+        });
+        LatestArc { inner }
+    }
+}
+
+#[cfg(feature = "tokio-rt")]
+#[derive(Debug)]
+struct LatestArcInner<T> {
+    latest: ArcSwap<T>,
+    /// Signals the background task when dropped.
+    _dropped_tx: tokio::sync::oneshot::Sender<()>,
+}
+
+/// Lock-free access to the latest value of a publisher.
+///
+/// Created by [`Publisher::into_latest_arc()`].
+///
+/// The value is updated asynchronously by a background task. Reading it
+/// with [`get()`](Self::get) never blocks, but may return a value that
+/// is slightly behind the current value of the publisher. All changes
+/// are eventually observed.
+#[cfg(feature = "tokio-rt")]
+#[derive(Debug, Clone)]
+pub struct LatestArc<T> {
+    inner: Arc<LatestArcInner<T>>,
+}
+
+#[cfg(feature = "tokio-rt")]
+impl<T> LatestArc<T> {
+    /// Get the latest value.
+    ///
+    /// Lock-free and never blocks.
+    #[must_use]
+    pub fn get(&self) -> Arc<T> {
+        self.inner.latest.load_full()
+    }
+}
+
+impl<T> Publisher<T> {
+    /// Convert into a publisher for _read-copy-update_ (RCU) workloads.
+    ///
+    /// The current value is cloned once. Subscribers of this publisher
+    /// are not affected and become orphaned after all other instances
+    /// of the publisher have been dropped.
+    #[must_use]
+    pub fn into_rcu(self) -> RcuPublisher<T>
+    where
+        T: Clone,
+    {
+        let current = Arc::new(T::clone(&self.read()));
+        RcuPublisher {
+            publisher: Publisher::new(Arc::clone(&current)),
+            current: ArcSwap::new(current),
+        }
+    }
+}
+
+/// Publisher for _read-copy-update_ (RCU) workloads.
+///
+/// Created by [`Publisher::into_rcu()`].
+///
+/// Reading the current value is lock-free. Updates replace the
+/// current value atomically and notify all subscribers.
+#[allow(missing_debug_implementations)]
+pub struct RcuPublisher<T> {
+    publisher: Publisher<Arc<T>>,
+    current: ArcSwap<T>,
+}
+
+impl<T> RcuPublisher<T> {
+    /// Obtain the current value.
+    ///
+    /// Lock-free and never blocks.
+    #[must_use]
+    pub fn read(&self) -> Arc<T> {
+        self.current.load_full()
+    }
+
+    /// Replace the current value and emit a change notification.
+    ///
+    /// The `update` closure receives the current value and returns
+    /// the new value. Concurrent updates are serialized.
+    pub fn update(&self, update: impl FnOnce(Arc<T>) -> Arc<T>) {
+        self.publisher.modify(|value| {
+            let next_value = update(Arc::clone(value));
+            self.current.store(Arc::clone(&next_value));
+            *value = next_value;
+            true
+        });
+    }
+
+    /// Subscribe to changes.
+    ///
+    /// See also: [`Publisher::subscribe()`]
+    #[must_use]
+    pub fn subscribe(&self) -> Subscriber<Arc<T>> {
+        self.publisher.subscribe()
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::Publisher;
+
+    #[tokio::test]
+    async fn rcu_update() {
+        let tx = Publisher::new(0).into_rcu();
+        let mut rx = tx.subscribe();
+        tx.update(|value| Arc::new(*value + 1));
+        assert_eq!(1, *tx.read());
+        assert_eq!(1, **rx.read_changed().await.unwrap());
+    }
+
+    #[cfg(feature = "tokio-rt")]
+    #[tokio::test]
+    async fn latest_arc_follows_changes() {
+        let tx = Publisher::new(0);
+        let latest = tx.into_latest_arc();
+        assert_eq!(0, *latest.get());
+        tx.write(1);
+        while *latest.get() != 1 {
+            tokio::task::yield_now().await;
+        }
+        drop(latest);
+        // The background task finishes without any further changes.
+        while tx.has_subscribers() {
+            tokio::task::yield_now().await;
+        }
+    }
+}
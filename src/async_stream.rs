@@ -1,7 +1,7 @@
 // SPDX-FileCopyrightText: The discro authors
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::{OrphanedSubscriberError, Subscriber};
+use crate::{OrphanedSubscriberError, Publisher, Subscriber};
 
 /// Observe modifications as a stream of changed values.
 ///
@@ -63,3 +63,373 @@ where
         }
     }
 }
+
+/// Observe modifications as a stream of changed values while maintaining state.
+///
+/// Returns a stream of changed values.
+///
+/// The `next_item_fn` closure is invoked on a borrowed value while the lock is held.
+/// It receives a mutable reference to the state that is passed from one invocation
+/// to the next, starting with `initial_state`.
+pub fn subscriber_into_changed_stream_with_state<'t, S, T, St>(
+    mut subscriber: Subscriber<S>,
+    initial_state: St,
+    mut next_item_fn: impl FnMut(&mut St, &S) -> T + Send + 't,
+) -> impl futures_core::Stream<Item = T> + Send + 't
+where
+    S: Send + Sync + 't,
+    T: Send + 't,
+    St: Send + 't,
+{
+    async_stream::stream! {
+        let next_item_fn = &mut next_item_fn;
+        let mut state = initial_state;
+        #[allow(clippy::while_let_loop)]
+        loop {
+            match subscriber.map_changed(|next| next_item_fn(&mut state, next)).await {
+                Ok(next_item) => {
+                    yield next_item
+                }
+                Err(OrphanedSubscriberError) => {
+                    // Stream exhausted after publisher disappeared.
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Observe modifications as a stream of changed values with periodic heartbeats.
+///
+/// Returns a stream that yields `Some` with a clone of each changed value
+/// and `None` whenever no change has been received for the duration of
+/// `heartbeat`.
+#[cfg(feature = "tokio-time")]
+pub fn subscriber_into_heartbeat_stream<'t, T>(
+    mut subscriber: Subscriber<T>,
+    heartbeat: std::time::Duration,
+) -> impl futures_core::Stream<Item = Option<T>> + Send + 't
+where
+    T: Clone + Send + Sync + 't,
+{
+    async_stream::stream! {
+        loop {
+            match tokio::time::timeout(heartbeat, subscriber.map_changed(T::clone)).await {
+                Ok(Ok(next_item)) => {
+                    yield Some(next_item);
+                }
+                Ok(Err(OrphanedSubscriberError)) => {
+                    // Stream exhausted after publisher disappeared.
+                    break;
+                }
+                Err(_) => {
+                    // Heartbeat without a change.
+                    yield None;
+                }
+            }
+        }
+    }
+}
+
+impl<T> Publisher<T> {
+    /// Subscribe to changes as a stream of values.
+    ///
+    /// Returns a stream that starts with the current value and then yields
+    /// every changed value until the publisher has been dropped.
+    ///
+    /// Shorthand for [`subscriber_into_changed_stream()`] with a
+    /// [`subscribe_changed()`](Self::subscribe_changed) subscriber that
+    /// clones the values.
+    pub fn subscribe_stream<'t>(&self) -> impl futures_core::Stream<Item = T> + Send + 't
+    where
+        T: Clone + Send + Sync + 't,
+    {
+        subscriber_into_changed_stream(self.subscribe_changed(), T::clone)
+    }
+
+    /// Subscribe to changes as a stream of `(previous, current)` value pairs.
+    ///
+    /// The first item contains the current value in both slots. Each subsequent
+    /// item contains the previously yielded value and the changed value.
+    pub fn diff_stream<'t>(&self) -> impl futures_core::Stream<Item = (T, T)> + Send + 't
+    where
+        T: Clone + Send + Sync + 't,
+    {
+        subscriber_into_changed_stream_with_state(
+            self.subscribe_changed(),
+            None,
+            |previous: &mut Option<T>, next: &T| {
+                let next = next.clone();
+                let previous = previous
+                    .replace(next.clone())
+                    .unwrap_or_else(|| next.clone());
+                (previous, next)
+            },
+        )
+    }
+}
+
+impl<T> Subscriber<T> {
+    /// Observe modifications as a stream of changed values until the publisher is dropped.
+    ///
+    /// Returns a stream of `(value, is_final)` pairs. After the publisher has been
+    /// dropped the last value is yielded once more with `is_final = true` before the
+    /// stream ends.
+    pub fn take_while_alive<'t>(mut self) -> impl futures_core::Stream<Item = (T, bool)> + Send + 't
+    where
+        T: Clone + Send + Sync + 't,
+    {
+        async_stream::stream! {
+            while let Ok(next_item) = self.map_changed(T::clone).await {
+                yield (next_item, false);
+            }
+            // Publisher has disappeared.
+            let final_item = T::clone(&self.read());
+            yield (final_item, true);
+        }
+    }
+
+    /// Observe modifications as a debounced stream of changed values.
+    ///
+    /// After a change has been received, subsequent changes are collected
+    /// until no new changes have been received for the duration of `window`.
+    /// Then the latest value is yielded.
+    ///
+    /// The latest value is yielded immediately after the publisher has been
+    /// dropped and the stream ends.
+    #[cfg(feature = "tokio-time")]
+    pub fn debounce_with_latest<'t>(
+        mut self,
+        window: std::time::Duration,
+    ) -> impl futures_core::Stream<Item = T> + Send + 't
+    where
+        T: Clone + Send + Sync + 't,
+    {
+        async_stream::stream! {
+            while self.changed().await.is_ok() {
+                let mut orphaned = false;
+                loop {
+                    match tokio::time::timeout(window, self.changed()).await {
+                        Ok(Ok(())) => {
+                            // Restart the debounce window.
+                            continue;
+                        }
+                        Ok(Err(OrphanedSubscriberError)) => {
+                            orphaned = true;
+                            break;
+                        }
+                        Err(_) => {
+                            // Debounce window has expired.
+                            break;
+                        }
+                    }
+                }
+                let next_item = T::clone(&self.read_ack());
+                yield next_item;
+                if orphaned {
+                    break;
+                }
+            }
+            // Publisher has disappeared.
+        }
+    }
+
+    /// Observe modifications as a stream of items while maintaining state.
+    ///
+    /// The `next_items_fn` closure is invoked on a borrowed value while the lock is held.
+    /// It receives a mutable reference to the state that is passed from one invocation
+    /// to the next, starting with `initial_state`. All items returned by an invocation
+    /// are yielded before the next changed value is processed.
+    pub fn flat_scan<'t, St, I, F>(
+        mut self,
+        initial_state: St,
+        mut next_items_fn: F,
+    ) -> impl futures_core::Stream<Item = I::Item> + Send + 't
+    where
+        T: Send + Sync + 't,
+        St: Send + 't,
+        F: FnMut(&mut St, &T) -> I + Send + 't,
+        I: IntoIterator + Send + 't,
+        I::IntoIter: Send + 't,
+        I::Item: Send + 't,
+    {
+        async_stream::stream! {
+            let next_items_fn = &mut next_items_fn;
+            let mut state = initial_state;
+            #[allow(clippy::while_let_loop)]
+            loop {
+                match self.map_changed(|next| next_items_fn(&mut state, next)).await {
+                    Ok(next_items) => {
+                        for next_item in next_items {
+                            yield next_item;
+                        }
+                    }
+                    Err(OrphanedSubscriberError) => {
+                        // Stream exhausted after publisher disappeared.
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Observe modifications as a filtered stream of items while maintaining state.
+    ///
+    /// Returns a stream of items for which `next_item_fn` returns `Some`.
+    ///
+    /// The `next_item_fn` closure is invoked on a borrowed value while the lock is held.
+    /// It receives a mutable reference to the state that is passed from one invocation
+    /// to the next, starting with `initial_state`.
+    pub fn scan_emit<'t, St, U, F>(
+        mut self,
+        initial_state: St,
+        mut next_item_fn: F,
+    ) -> impl futures_core::Stream<Item = U> + Send + 't
+    where
+        T: Send + Sync + 't,
+        St: Send + 't,
+        U: Send + 't,
+        F: FnMut(&mut St, &T) -> Option<U> + Send + 't,
+    {
+        async_stream::stream! {
+            let next_item_fn = &mut next_item_fn;
+            let mut state = initial_state;
+            #[allow(clippy::while_let_loop)]
+            loop {
+                match self.filter_map_changed(|next| next_item_fn(&mut state, next)).await {
+                    Ok(next_item) => {
+                        yield next_item
+                    }
+                    Err(OrphanedSubscriberError) => {
+                        // Stream exhausted after publisher disappeared.
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tokio-time"))]
+mod tests {
+    use std::{future::poll_fn, pin::pin, time::Duration};
+
+    use futures_core::Stream;
+
+    use crate::{subscriber_into_heartbeat_stream, Publisher};
+
+    #[tokio::test]
+    async fn debounce_with_latest() {
+        let tx = Publisher::new(0);
+        let mut stream = pin!(tx
+            .subscribe()
+            .debounce_with_latest(Duration::from_millis(10)));
+        tx.write(1);
+        tx.write(2);
+        assert_eq!(Some(2), poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+        tx.write(3);
+        drop(tx);
+        assert_eq!(Some(3), poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+        assert_eq!(None, poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+    }
+
+    #[tokio::test]
+    async fn diff_stream() {
+        let tx = Publisher::new(0);
+        let mut stream = pin!(tx.diff_stream());
+        assert_eq!(
+            Some((0, 0)),
+            poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+        );
+        tx.write(1);
+        assert_eq!(
+            Some((0, 1)),
+            poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+        );
+        tx.write(2);
+        assert_eq!(
+            Some((1, 2)),
+            poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+        );
+    }
+
+    #[tokio::test]
+    async fn take_while_alive() {
+        let tx = Publisher::new(0);
+        let mut stream = pin!(tx.subscribe().take_while_alive());
+        tx.write(1);
+        assert_eq!(
+            Some((1, false)),
+            poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+        );
+        drop(tx);
+        assert_eq!(
+            Some((1, true)),
+            poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+        );
+        assert_eq!(None, poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+    }
+
+    #[tokio::test]
+    async fn heartbeat_stream() {
+        let tx = Publisher::new(0);
+        let mut stream = pin!(subscriber_into_heartbeat_stream(
+            tx.subscribe(),
+            Duration::from_millis(1)
+        ));
+        // Heartbeat without a change.
+        assert_eq!(
+            Some(None),
+            poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+        );
+        tx.write(1);
+        assert_eq!(
+            Some(Some(1)),
+            poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+        );
+        drop(tx);
+        assert_eq!(None, poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+    }
+
+    #[tokio::test]
+    async fn flat_scan() {
+        let tx = Publisher::new(0);
+        // Repeat each value as often as the number of changes received so far.
+        let mut stream = pin!(tx.subscribe().flat_scan(0, |count, value| {
+            *count += 1;
+            std::iter::repeat(*value).take(*count)
+        }));
+        tx.write(5);
+        assert_eq!(Some(5), poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+        tx.write(7);
+        assert_eq!(Some(7), poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+        assert_eq!(Some(7), poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+        drop(tx);
+        assert_eq!(None, poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+    }
+
+    #[tokio::test]
+    async fn scan_emit() {
+        let tx = Publisher::new(0);
+        // Only emit values that exceed the maximum received so far.
+        let mut stream = pin!(tx.subscribe().scan_emit(0, |max, value| {
+            (*value > *max).then(|| {
+                *max = *value;
+                *value
+            })
+        }));
+        tx.write(3);
+        assert_eq!(Some(3), poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+        tx.write(2);
+        assert!(tokio::time::timeout(
+            Duration::from_millis(1),
+            poll_fn(|cx| stream.as_mut().poll_next(cx))
+        )
+        .await
+        .is_err());
+        tx.write(5);
+        assert_eq!(Some(5), poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+        drop(tx);
+        assert_eq!(None, poll_fn(|cx| stream.as_mut().poll_next(cx)).await);
+    }
+}
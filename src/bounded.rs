@@ -0,0 +1,195 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Subscribers with a bounded queue of changed values.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+};
+
+use tokio::sync::Notify;
+
+use crate::{CallbackHandle, OrphanedSubscriberError, Publisher, Subscriber};
+
+/// Handling of changed values when the queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundedOverflow {
+    /// Drop the oldest queued value
+    DropOldest,
+
+    /// Drop the newest, changed value
+    DropNewest,
+}
+
+#[derive(Debug)]
+struct Queue<T> {
+    capacity: usize,
+    overflow: BoundedOverflow,
+    items: Mutex<VecDeque<T>>,
+    closed: AtomicBool,
+    notify: Notify,
+}
+
+impl<T> Queue<T> {
+    fn push(&self, item: T) {
+        {
+            let mut items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+            if items.len() >= self.capacity {
+                match self.overflow {
+                    BoundedOverflow::DropOldest => {
+                        items.pop_front();
+                    }
+                    BoundedOverflow::DropNewest => {
+                        return;
+                    }
+                }
+            }
+            items.push_back(item);
+        }
+        self.notify.notify_one();
+    }
+
+    fn pop(&self) -> Option<T> {
+        let mut items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+        items.pop_front()
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.notify.notify_one();
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+}
+
+/// Closes the queue after the callback has been dropped.
+struct QueueSender<T>(Arc<Queue<T>>);
+
+impl<T> Drop for QueueSender<T> {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+impl<T> Publisher<T>
+where
+    T: Clone + Send + 'static,
+{
+    /// Subscribe to changes with a bounded queue.
+    ///
+    /// Every changed value is cloned and queued. If the queue is full,
+    /// either the oldest or the newest value is dropped, depending on
+    /// `overflow`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    #[must_use]
+    pub fn subscribe_bounded(
+        &self,
+        capacity: usize,
+        overflow: BoundedOverflow,
+    ) -> BoundedSubscriber<T> {
+        assert!(capacity > 0, "capacity must be positive");
+        let queue = Arc::new(Queue {
+            capacity,
+            overflow,
+            items: Mutex::new(VecDeque::with_capacity(capacity)),
+            closed: AtomicBool::new(false),
+            notify: Notify::new(),
+        });
+        let sender = QueueSender(Arc::clone(&queue));
+        let callback_handle = self.on_change_callback(move |value| sender.0.push(value.clone()));
+        BoundedSubscriber {
+            queue,
+            _callback_handle: callback_handle,
+            _subscriber: self.subscribe(),
+        }
+    }
+}
+
+/// Subscriber that receives all changed values through a bounded queue.
+///
+/// Created by [`Publisher::subscribe_bounded()`].
+///
+/// Unlike [`Subscriber`](crate::Subscriber) changes are not coalesced
+/// unless the queue overflows.
+#[derive(Debug)]
+pub struct BoundedSubscriber<T> {
+    queue: Arc<Queue<T>>,
+    _callback_handle: CallbackHandle,
+    /// Only needed for being accounted as a subscriber of the publisher.
+    _subscriber: Subscriber<T>,
+}
+
+impl<T> BoundedSubscriber<T> {
+    /// Receive the next changed value from the queue.
+    ///
+    /// Queued values are still received after the publisher has been dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the queue is empty and the publisher has been dropped.
+    pub async fn changed(&mut self) -> Result<T, OrphanedSubscriberError> {
+        loop {
+            if let Some(next_value) = self.queue.pop() {
+                return Ok(next_value);
+            }
+            if self.queue.is_closed() {
+                return Err(OrphanedSubscriberError);
+            }
+            self.queue.notify.notified().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundedOverflow;
+    use crate::Publisher;
+
+    #[tokio::test]
+    async fn drop_oldest() {
+        let tx = Publisher::new(0);
+        let mut rx = tx.subscribe_bounded(2, BoundedOverflow::DropOldest);
+        tx.write(1);
+        tx.write(2);
+        tx.write(3);
+        drop(tx);
+        assert_eq!(2, rx.changed().await.unwrap());
+        assert_eq!(3, rx.changed().await.unwrap());
+        assert!(rx.changed().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn connected_as_subscriber() {
+        let tx = Publisher::new(0);
+        let mut rx = tx.subscribe_bounded(1, BoundedOverflow::DropOldest);
+        assert!(tx.has_subscribers());
+        assert!(tx.write_if_connected(5));
+        assert_eq!(5, rx.changed().await.unwrap());
+        assert!(tx.write_lazy(|| 6));
+        assert_eq!(6, rx.changed().await.unwrap());
+        drop(rx);
+        assert!(!tx.has_subscribers());
+    }
+
+    #[tokio::test]
+    async fn drop_newest() {
+        let tx = Publisher::new(0);
+        let mut rx = tx.subscribe_bounded(2, BoundedOverflow::DropNewest);
+        tx.write(1);
+        tx.write(2);
+        tx.write(3);
+        assert_eq!(1, rx.changed().await.unwrap());
+        assert_eq!(2, rx.changed().await.unwrap());
+        tx.write(4);
+        assert_eq!(4, rx.changed().await.unwrap());
+    }
+}
@@ -0,0 +1,49 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::ops::Deref;
+
+use crate::Publisher;
+
+impl<T> Publisher<T> {
+    /// Write a final value when dropped.
+    ///
+    /// Subscribers receive `close_value` as the last change before the
+    /// publisher is dropped. This allows them to distinguish an explicit
+    /// shutdown from an accidental drop by using a sentinel value.
+    #[must_use]
+    pub const fn with_close_value(self, close_value: T) -> ClosingPublisher<T> {
+        ClosingPublisher {
+            publisher: self,
+            close_value: Some(close_value),
+        }
+    }
+}
+
+/// Publisher that writes a final value when dropped.
+///
+/// Created by [`Publisher::with_close_value()`].
+///
+/// The close value is written when this instance is dropped, even if
+/// other clones of the wrapped publisher are still alive.
+#[allow(missing_debug_implementations)]
+pub struct ClosingPublisher<T> {
+    publisher: Publisher<T>,
+    close_value: Option<T>,
+}
+
+impl<T> Deref for ClosingPublisher<T> {
+    type Target = Publisher<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.publisher
+    }
+}
+
+impl<T> Drop for ClosingPublisher<T> {
+    fn drop(&mut self) {
+        if let Some(close_value) = self.close_value.take() {
+            self.publisher.write(close_value);
+        }
+    }
+}
@@ -9,7 +9,7 @@
 
 use std::{marker::PhantomData, ops::Deref};
 
-use super::OrphanedSubscriberError;
+use super::{OnceSentinel, OrphanedSubscriberError, WriteError};
 
 /// A borrowed reference to the shared value.
 ///
@@ -61,12 +61,48 @@ impl<T> Publisher<T> {
         unimplemented!()
     }
 
+    /// Create a new publisher with a hook that is invoked on every change.
+    ///
+    /// Same as registering a callback with [`on_change_callback()`](Self::on_change_callback)
+    /// that could never be unregistered. The same restrictions apply.
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn new_with_hook(
+        #[allow(unused_variables)] initial_value: T,
+        #[allow(unused_variables)] hook: impl Fn(&T) + Send + Sync + 'static,
+    ) -> Self {
+        unimplemented!()
+    }
+
+    /// Create a new publisher with an initial value derived from another.
+    ///
+    /// The initial value is computed from the current value of `source`
+    /// while it is read-locked.
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn new_derived<U>(
+        #[allow(unused_variables)] source: &Observer<U>,
+        #[allow(unused_variables)] derive: impl FnOnce(&U) -> T,
+    ) -> Self {
+        unimplemented!()
+    }
+
     /// Create an [`Observer`] for read-only access.
     #[must_use]
     pub fn observe(&self) -> Observer<T> {
         unimplemented!()
     }
 
+    /// Create an [`Observer`] and obtain a reference to the current value.
+    ///
+    /// The returned reference holds a read lock until it is dropped.
+    /// No writes could happen until then, i.e. the value is consistent
+    /// with the state of the observer when it has been created.
+    #[must_use]
+    pub fn observe_and_read(&self) -> (Observer<T>, Ref<T>) {
+        unimplemented!()
+    }
+
     /// Subscribe to this publisher
     ///
     /// See also: [`Observer::subscribe()`]
@@ -92,6 +128,16 @@ impl<T> Publisher<T> {
         unimplemented!()
     }
 
+    /// Observe errors that occurred while writing.
+    ///
+    /// Back-ends with fallible writes publish the last write error
+    /// through the returned observer. For back-ends with infallible
+    /// writes the value is always `None` and never changes.
+    #[must_use]
+    pub fn error_channel(&self) -> Observer<Option<WriteError>> {
+        unimplemented!()
+    }
+
     /// Obtain a reference to the current value.
     ///
     /// See also: [`Observer::read()`]
@@ -110,6 +156,85 @@ impl<T> Publisher<T> {
         unimplemented!()
     }
 
+    /// Same as [`write()`](Self::write), but for fire-and-forget writes.
+    ///
+    /// Write errors of fallible back-ends are logged instead of returned.
+    /// All current back-ends are infallible.
+    #[cfg(feature = "tracing")]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn write_or_log(&self, #[allow(unused_variables)] new_value: T) {
+        unimplemented!()
+    }
+
+    /// Overwrite the current value with a lazily computed value
+    /// only if subscribers are connected.
+    ///
+    /// The `new_value` closure is only invoked if the publisher has
+    /// subscribers. Returns `true` if the value has been written.
+    ///
+    /// See also: [`write_if_connected()`](Self::write_if_connected)
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn write_lazy(&self, #[allow(unused_variables)] new_value: impl FnOnce() -> T) -> bool {
+        unimplemented!()
+    }
+
+    /// Overwrite the current value only if subscribers are connected.
+    ///
+    /// Skips the write and returns `false` if the publisher has no
+    /// subscribers. Otherwise behaves like [`write()`](Self::write)
+    /// and returns `true`.
+    ///
+    /// Intended for values that are only meaningful while someone is
+    /// listening. Values written while no subscribers are connected are
+    /// discarded and are not visible to subscribers connecting later.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn write_if_connected(&self, #[allow(unused_variables)] new_value: T) -> bool {
+        unimplemented!()
+    }
+
+    /// Overwrite the current value unless a subscriber is saturated.
+    ///
+    /// Skips the write and returns `false` while any subscriber has signaled
+    /// saturation with [`Subscriber::signal_saturation()`]. Otherwise
+    /// behaves like [`write()`](Self::write) and returns `true`.
+    /// Dropped subscribers are not considered.
+    ///
+    /// This is an advisory, best-effort back-pressure mechanism.
+    /// Other writes are not affected.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn write_if_unsaturated(&self, #[allow(unused_variables)] new_value: T) -> bool {
+        unimplemented!()
+    }
+
+    /// Consume the publisher and return the current value.
+    ///
+    /// Returns the current value if no subscribers are connected.
+    /// Otherwise the publisher is returned as the error.
+    ///
+    /// The value is cloned, because other instances of the publisher
+    /// or observers might still share it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the publisher if subscribers are connected.
+    pub fn try_into_value(self) -> Result<T, Self>
+    where
+        T: Clone,
+    {
+        unimplemented!()
+    }
+
+    /// Overwrite the current value for the last time.
+    ///
+    /// Consumes the publisher after writing the new value and emitting
+    /// a change notification. Observers and subscribers stay connected.
+    /// The value becomes read-only unless other instances of the
+    /// publisher exist.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn write_once(self, #[allow(unused_variables)] new_value: impl Into<T>) -> OnceSentinel {
+        unimplemented!()
+    }
+
     /// Replace and return the current value with a new value
     /// and emit a change notification.
     ///
@@ -123,6 +248,25 @@ impl<T> Publisher<T> {
         unimplemented!()
     }
 
+    /// Replace the current value with a new value computed from it
+    /// and emit a change notification.
+    ///
+    /// The `map` closure receives a clone of the current value and returns
+    /// the new value. Returns the previous value. The value is write-locked
+    /// during the invocation of `map`.
+    ///
+    /// Unlike [`modify`](Self::modify) the new value is not modified in-place,
+    /// which is useful for immutable value types.
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn replace_map<F>(&self, #[allow(unused_variables)] map: F) -> T
+    where
+        F: FnOnce(T) -> T,
+        T: Clone,
+    {
+        unimplemented!()
+    }
+
     /// Modify the current value in-place and conditionally emit a
     /// change notification.
     ///
@@ -149,10 +293,240 @@ impl<T> Publisher<T> {
         unimplemented!()
     }
 
+    /// Update the current value with a _compare-and-swap_ loop.
+    ///
+    /// The new value is computed by `update` from a clone of the current
+    /// value without holding a lock. The current value is then replaced
+    /// only if it has not been modified concurrently in the meantime.
+    /// Otherwise `update` is invoked again on the modified value.
+    ///
+    /// A change notification is only emitted if the new value differs
+    /// from the current value.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn atomic_update<F>(&self, #[allow(unused_variables)] update: F)
+    where
+        F: Fn(&T) -> T,
+        T: Clone + PartialEq,
+    {
+        unimplemented!()
+    }
+
+    /// Invoke a callback on every change.
+    ///
+    /// The `on_change` closure is invoked with the new value after every
+    /// write that emits a change notification. The value is still
+    /// write-locked during the invocation. Callbacks are shared by all
+    /// instances of the publisher.
+    ///
+    /// The callback is unregistered when the returned handle is dropped.
+    ///
+    /// Callbacks must not access the shared value. Otherwise a deadlock
+    /// might occur! Registering other callbacks or dropping a
+    /// [`CallbackHandle`] from within a callback is permitted.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn on_change_callback(
+        &self,
+        #[allow(unused_variables)] on_change: impl Fn(&T) + Send + Sync + 'static,
+    ) -> CallbackHandle
+    where
+        T: 'static,
+    {
+        unimplemented!()
+    }
+
     /// Mark the current value as _modified_, i.e. _changed_ for all subscribers.
     pub fn set_modified(&self) {
         unimplemented!()
     }
+
+    /// Try to obtain exclusive write access without blocking.
+    ///
+    /// Returns `None` if the write lock is currently contested and
+    /// acquiring it would block. Back-ends that never block for an
+    /// unbounded period of time while writing always return `Some`.
+    #[must_use]
+    pub fn try_lock_write(&self) -> Option<WriteGuard<'_, T>> {
+        unimplemented!()
+    }
+
+    /// Overwrite the current value if the new value is different.
+    ///
+    /// A change notification is only emitted if the new value differs
+    /// from the current value. Returns `true` if the value has been
+    /// replaced and a change notification has been emitted.
+    ///
+    /// Named after `tokio::sync::watch::Sender::send_if_modified()`.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn send_if_different(&self, #[allow(unused_variables)] new_value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        unimplemented!()
+    }
+
+    /// Write a sequence of values and skip duplicates.
+    ///
+    /// Each item is written separately. A change notification is only
+    /// emitted if the item differs from the current value.
+    ///
+    /// Returns the number of emitted change notifications.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn write_sequence_deduplicated<I>(&self, #[allow(unused_variables)] items: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+        T: PartialEq,
+    {
+        unimplemented!()
+    }
+
+    /// Write the next value from a fixed list of values.
+    ///
+    /// Cycles through `values` by writing the value at the position of an
+    /// internal counter (modulo the number of values) and then incrementing
+    /// the counter. Returns the index of the written value.
+    ///
+    /// The counter is shared by all instances of the publisher.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` is empty.
+    pub fn write_cycle(&self, #[allow(unused_variables)] values: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        unimplemented!()
+    }
+
+    /// Write a sequence of values and emit a single change notification.
+    ///
+    /// Only the last item is written. The iterator is consumed before
+    /// acquiring the write lock and intermediate values are never visible
+    /// to subscribers.
+    ///
+    /// No change notification is emitted if `items` is empty.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn write_batch_last<I>(&self, #[allow(unused_variables)] items: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        unimplemented!()
+    }
+}
+
+impl<T, E> Publisher<Result<T, E>> {
+    /// Derive a publisher that transforms the error type.
+    ///
+    /// The derived publisher is updated on every change of this publisher
+    /// by mapping the error with `map_err`. Consumes this publisher, i.e.
+    /// subsequent changes are written through other instances of the
+    /// publisher.
+    ///
+    /// Subscribers of the derived publisher are orphaned after all its
+    /// instances have been dropped, independent of this publisher.
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn map_err_published<F, E2>(
+        self,
+        #[allow(unused_variables)] map_err: F,
+    ) -> Publisher<Result<T, E2>>
+    where
+        F: Fn(E) -> E2 + Send + Sync + 'static,
+        T: Clone + Send + Sync + 'static,
+        E: Clone + 'static,
+        E2: Send + Sync + 'static,
+    {
+        unimplemented!()
+    }
+}
+
+/// Handle of a registered callback.
+///
+/// Obtained by [`Publisher::on_change_callback()`].
+///
+/// Unregisters the callback when dropped.
+#[derive(Debug)]
+pub struct CallbackHandle;
+
+/// Write access to a shared value.
+///
+/// Obtained by [`Publisher::try_lock_write()`].
+///
+/// Depending on the back-end the guard might not hold the write lock
+/// itself. In this case every operation acquires the lock separately.
+#[allow(missing_debug_implementations)]
+pub struct WriteGuard<'a, T> {
+    phantom: PhantomData<&'a T>,
+}
+
+impl<T> WriteGuard<'_, T> {
+    /// See also: [`Publisher::write()`]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn write(&self, #[allow(unused_variables)] new_value: impl Into<T>) {
+        unimplemented!()
+    }
+
+    /// See also: [`Publisher::replace()`]
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn replace(&self, #[allow(unused_variables)] new_value: impl Into<T>) -> T {
+        unimplemented!()
+    }
+
+    /// See also: [`Publisher::modify()`]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn modify<M>(&self, #[allow(unused_variables)] modify: M) -> bool
+    where
+        M: FnOnce(&mut T) -> bool,
+    {
+        unimplemented!()
+    }
+}
+
+/// Read the current values of two publishers consistently.
+///
+/// Both read locks are held until the returned references are dropped.
+/// This prevents any modifications in between reading the two values.
+///
+/// The locks are always acquired in the same order, i.e. sorted by the
+/// memory address of the shared values, independent of the order of the
+/// arguments. This prevents deadlocks when different tasks read the same
+/// pair of publishers concurrently.
+///
+/// Both publishers must belong to different channels. Otherwise reading
+/// the same value twice might cause a deadlock!
+#[must_use]
+pub fn read_consistent_pair<'a, A, B>(
+    #[allow(unused_variables)] pa: &'a Publisher<A>,
+    #[allow(unused_variables)] pb: &'a Publisher<B>,
+) -> (Ref<A>, Ref<B>) {
+    unimplemented!()
+}
+
+/// Swap the current values of two publishers.
+///
+/// Emits a change notification for both publishers. Both write locks
+/// are held while swapping the values.
+///
+/// The locks are acquired in the same order as for [`read_consistent_pair()`]
+/// to prevent deadlocks.
+///
+/// Nothing happens if both publishers belong to the same channel.
+pub fn swap_publishers<T>(
+    #[allow(unused_variables)] a: &Publisher<T>,
+    #[allow(unused_variables)] b: &Publisher<T>,
+) {
+    unimplemented!()
+}
+
+/// Mark the current values of multiple publishers as _modified_.
+///
+/// Invokes [`Publisher::set_modified()`] for each publisher in order.
+/// Back-ends may reduce the overhead by amortizing lock acquisitions.
+///
+/// The change notifications are **not** emitted atomically. Subscribers
+/// might observe some publishers as modified before others.
+pub fn notify_batch<T>(#[allow(unused_variables)] publishers: &[&Publisher<T>]) {
+    unimplemented!()
 }
 
 /// Observer of a shared value.
@@ -241,14 +615,40 @@ impl<T> Subscriber<T> {
         unimplemented!()
     }
 
+    /// Signal that this subscriber is saturated.
+    ///
+    /// Writes with [`Publisher::write_if_unsaturated()`] are skipped until
+    /// the saturation is cleared or the subscriber is dropped. The saturation
+    /// state is shared with clones of this subscriber, but not with other
+    /// subscribers.
+    pub fn signal_saturation(&self) {
+        unimplemented!()
+    }
+
+    /// Clear a previously signaled saturation.
+    ///
+    /// See also: [`signal_saturation()`](Self::signal_saturation)
+    pub fn clear_saturation(&self) {
+        unimplemented!()
+    }
+
+    /// Check if a change is available without waiting.
+    ///
+    /// Returns `true` if the current value is marked as _changed_, i.e. if
+    /// [`changed()`](Self::changed) would resolve immediately.
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        unimplemented!()
+    }
+
     /// Receive change notifications for the shared value.
     ///
     /// Waits for a change notification, then marks the newest value as seen.
     ///
-    /// After subscribing, the first call to this method returns immediately.
-    /// The current value after subscribing is always considered as _changed_.
-    /// This also applies when creating a new subscriber by cloning an existing
-    /// subscriber!
+    /// Returns immediately if the current value is considered as _changed_,
+    /// e.g. after subscribing with [`Observer::subscribe_changed()`].
+    /// Subscribers created by cloning an existing subscriber inherit the
+    /// _changed_ state of the original subscriber.
     ///
     /// # Errors
     ///
@@ -302,10 +702,33 @@ impl<T> Subscriber<T> {
             }
         }
     }
+
+    /// Capture the next, changed value by converting it.
+    ///
+    /// The value is converted while the read lock is held.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the publisher has been dropped.
+    pub async fn read_changed_into<U>(&mut self) -> Result<U, OrphanedSubscriberError>
+    where
+        U: for<'a> From<&'a T>,
+    {
+        self.map_changed(|next| U::from(next)).await
+    }
 }
 
+/// Cloned subscribers start with the same _changed_ state as the original.
+///
+/// Cloning a subscriber after acknowledging the current value with
+/// [`read_ack()`](Subscriber::read_ack) results in a subscriber that
+/// does not consider the current value as _changed_. Use
+/// [`mark_changed()`](Subscriber::mark_changed) on the clone to
+/// receive the current value immediately.
 impl<T> Clone for Subscriber<T> {
     fn clone(&self) -> Self {
-        unimplemented!()
+        Self {
+            phantom: PhantomData,
+        }
     }
 }
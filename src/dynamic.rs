@@ -0,0 +1,140 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Type-erased observers and subscribers.
+
+use std::{future::Future, pin::Pin};
+
+use crate::{Observer, OrphanedSubscriberError, Subscriber};
+
+/// Future returned by [`DynSubscriber::changed_dyn()`].
+pub type DynChanged<'a> =
+    Pin<Box<dyn Future<Output = Result<(), OrphanedSubscriberError>> + Send + 'a>>;
+
+/// Object-safe interface of an [`Observer`].
+///
+/// Allows to store observers of different channels or back-ends
+/// as trait objects, e.g. in a `Vec<Box<dyn DynObserver<T>>>`.
+pub trait DynObserver<T>: Send + Sync {
+    /// Obtain a clone of the current value.
+    ///
+    /// See also: [`Observer::read()`]
+    #[must_use]
+    fn read_cloned(&self) -> T
+    where
+        T: Clone;
+
+    /// Subscribe to changes.
+    ///
+    /// See also: [`Observer::subscribe()`]
+    #[must_use]
+    fn subscribe_dyn(&self) -> Box<dyn DynSubscriber<T>>;
+}
+
+/// Object-safe interface of a [`Subscriber`].
+pub trait DynSubscriber<T>: Send + Sync {
+    /// Obtain a clone of the current value.
+    ///
+    /// See also: [`Subscriber::read()`]
+    #[must_use]
+    fn read_cloned(&self) -> T
+    where
+        T: Clone;
+
+    /// Obtain a clone of the current value and acknowledge it.
+    ///
+    /// See also: [`Subscriber::read_ack()`]
+    #[must_use]
+    fn read_ack_cloned(&mut self) -> T
+    where
+        T: Clone;
+
+    /// Mark the current value as _changed_.
+    ///
+    /// See also: [`Subscriber::mark_changed()`]
+    fn mark_changed(&mut self);
+
+    /// Receive change notifications.
+    ///
+    /// See also: [`Subscriber::changed()`]
+    fn changed_dyn(&mut self) -> DynChanged<'_>;
+}
+
+impl<T> Observer<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Convert into a type-erased observer.
+    #[must_use]
+    pub fn into_dyn(self) -> Box<dyn DynObserver<T>> {
+        Box::new(self)
+    }
+}
+
+impl<T> DynObserver<T> for Observer<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn read_cloned(&self) -> T
+    where
+        T: Clone,
+    {
+        T::clone(&self.read())
+    }
+
+    fn subscribe_dyn(&self) -> Box<dyn DynSubscriber<T>> {
+        Box::new(self.subscribe())
+    }
+}
+
+impl<T> DynSubscriber<T> for Subscriber<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn read_cloned(&self) -> T
+    where
+        T: Clone,
+    {
+        T::clone(&self.read())
+    }
+
+    fn read_ack_cloned(&mut self) -> T
+    where
+        T: Clone,
+    {
+        T::clone(&self.read_ack())
+    }
+
+    fn mark_changed(&mut self) {
+        Subscriber::mark_changed(self);
+    }
+
+    fn changed_dyn(&mut self) -> DynChanged<'_> {
+        Box::pin(self.changed())
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use super::DynObserver;
+    use crate::Publisher;
+
+    #[tokio::test]
+    async fn store_observers_as_trait_objects() {
+        let tx1 = Publisher::new(1);
+        let tx2 = Publisher::new(2);
+        let observers: Vec<Box<dyn DynObserver<i32>>> =
+            vec![tx1.observe().into_dyn(), tx2.observe().into_dyn()];
+        assert_eq!(
+            vec![1, 2],
+            observers
+                .iter()
+                .map(|observer| observer.read_cloned())
+                .collect::<Vec<_>>()
+        );
+        let mut rx = observers[1].subscribe_dyn();
+        tx2.write(3);
+        rx.changed_dyn().await.unwrap();
+        assert_eq!(3, rx.read_ack_cloned());
+    }
+}
@@ -0,0 +1,115 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::ops::Deref;
+
+use crate::{Observer, Publisher, Subscriber};
+
+impl<T> Publisher<T> {
+    /// Create a new publisher that never changes.
+    ///
+    /// See also: [`FrozenPublisher`]
+    #[must_use]
+    pub fn new_frozen(value: T) -> FrozenPublisher<T> {
+        FrozenPublisher {
+            publisher: Publisher::new(value),
+        }
+    }
+}
+
+/// Publisher of a value that never changes.
+///
+/// Created by [`Publisher::new_frozen()`].
+///
+/// All write methods are no-ops. Subscribers will never receive any change
+/// notifications. Useful as a placeholder or default value source.
+#[allow(missing_debug_implementations)]
+pub struct FrozenPublisher<T> {
+    publisher: Publisher<T>,
+}
+
+impl<T> FrozenPublisher<T> {
+    /// Create an [`Observer`] for read-only access.
+    #[must_use]
+    pub fn observe(&self) -> Observer<T> {
+        self.publisher.observe()
+    }
+
+    /// Check if the publisher has subscribers.
+    ///
+    /// See also: [`Publisher::has_subscribers()`]
+    #[must_use]
+    pub fn has_subscribers(&self) -> bool {
+        self.publisher.has_subscribers()
+    }
+
+    /// Subscribe to (the absence of) changes.
+    #[must_use]
+    pub fn subscribe(&self) -> Subscriber<T> {
+        self.publisher.subscribe()
+    }
+
+    /// Subscribe to (the absence of) changes.
+    ///
+    /// The initial value is considered as _changed_ once, but no
+    /// subsequent changes will ever be received.
+    ///
+    /// See also: [`Publisher::subscribe_changed()`]
+    #[must_use]
+    pub fn subscribe_changed(&self) -> Subscriber<T> {
+        self.publisher.subscribe_changed()
+    }
+
+    /// Obtain a reference to the value.
+    ///
+    /// See also: [`Publisher::read()`]
+    #[must_use]
+    pub fn read(&self) -> impl Deref<Target = T> + '_ {
+        self.publisher.read()
+    }
+
+    /// Ignored.
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    pub fn write(&self, #[allow(unused_variables)] new_value: T) {}
+
+    /// Ignored.
+    ///
+    /// The `modify` closure is never invoked. Always returns `false`.
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    pub fn modify<M>(&self, #[allow(unused_variables)] modify: M) -> bool
+    where
+        M: FnOnce(&mut T) -> bool,
+    {
+        false
+    }
+
+    /// Ignored.
+    #[allow(clippy::unused_self)]
+    pub const fn set_modified(&self) {}
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use std::time::Duration;
+
+    use crate::Publisher;
+
+    #[tokio::test]
+    async fn never_changed() {
+        let tx = Publisher::new_frozen(0);
+        assert!(!tx.has_subscribers());
+        let mut rx = tx.subscribe_changed();
+        assert!(tx.has_subscribers());
+        assert_eq!(0, *rx.read_changed().await.unwrap());
+        tx.write(1);
+        assert!(!tx.modify(|value| {
+            *value = 2;
+            true
+        }));
+        tx.set_modified();
+        assert!(tokio::time::timeout(Duration::from_millis(1), rx.changed())
+            .await
+            .is_err());
+        assert_eq!(0, *tx.read());
+    }
+}
@@ -3,12 +3,31 @@
 
 //! Discrete observables for asynchronous *Functional Reactive Programming* (FRP).
 //!
+//! A [`Publisher`] holds a shared value. Changes are observed by
+//! [`Subscriber`]s. The simplest way to observe all changes is
+//! to subscribe to a stream of values with `Publisher::subscribe_stream()`
+//! (requires the `async-stream` feature).
+//!
 //! ## Crate Features
 //!
 //! One of the following (mutually exclusive) features must be enabled to select
 //! a concrete implementation:
 //!
 //! - `tokio` implementation based on [`tokio::sync::watch`](https://docs.rs/tokio/latest/tokio/sync/watch/)
+//!
+//! Additional back-ends with separate types:
+//!
+//! - `parking-lot` synchronous implementation based on [`parking_lot`](https://docs.rs/parking_lot/latest/parking_lot/)
+//!   that does not depend on `tokio`, e.g. `ParkingLotPublisher`
+//!
+//! Optional features:
+//!
+//! - `tokio-rt` enables functions that spawn tasks on a Tokio runtime (implies `tokio`)
+//! - `tokio-signal` enables tasklets that finish on Unix signals (implies `tokio`)
+//! - `tokio-time` enables time-based operators (implies `tokio`)
+//! - `arc-swap` enables lock-free access to shared values based on [`arc-swap`](https://docs.rs/arc-swap/latest/arc_swap/)
+//! - `tracing` enables `Publisher::write_or_log()` for fire-and-forget writes (currently the same as `Publisher::write()`)
+//! - `serde-json` enables reading and writing shared values as JSON based on [`serde_json`](https://docs.rs/serde_json/latest/serde_json/)
 
 use thiserror::Error;
 
@@ -25,18 +44,75 @@ mod traits;
 #[error("disconnected from publisher")]
 pub struct OrphanedSubscriberError;
 
+/// Indicates that a value has been written exactly once.
+///
+/// See also: `Publisher::write_once()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnceSentinel;
+
+/// An error that occurred while writing a shared value.
+///
+/// See also: `Publisher::error_channel()`
+pub type WriteError = Box<dyn std::error::Error + Send + Sync>;
+
 pub(crate) mod subscriber;
 
+mod closing;
+pub use self::closing::ClosingPublisher;
+
+mod dynamic;
+pub use self::dynamic::{DynChanged, DynObserver, DynSubscriber};
+
+mod frozen;
+pub use self::frozen::FrozenPublisher;
+
+mod logging;
+pub use self::logging::LoggingSubscriber;
+
+mod select;
+pub use self::select::SelectChanged;
+
+mod timed;
+pub use self::timed::TimedSubscriber;
+
+mod vec;
+pub use self::vec::VecPublisherExt;
+
 #[cfg(feature = "tokio")]
 mod tokio;
 
 #[cfg(feature = "tokio")]
 pub use self::tokio::*;
 
+#[cfg(feature = "tokio")]
+mod bounded;
+
+#[cfg(feature = "tokio")]
+pub use self::bounded::{BoundedOverflow, BoundedSubscriber};
+
 #[cfg(feature = "async-stream")]
 mod async_stream;
 
 #[cfg(feature = "async-stream")]
 pub use self::async_stream::*;
 
+#[cfg(feature = "arc-swap")]
+mod arc_swap;
+
+#[cfg(feature = "arc-swap")]
+pub use self::arc_swap::*;
+
+#[cfg(feature = "parking-lot")]
+mod parking_lot;
+
+#[cfg(feature = "parking-lot")]
+pub use self::parking_lot::{
+    ParkingLotObserver, ParkingLotPublisher, ParkingLotRef, ParkingLotSubscriber,
+};
+
+#[cfg(feature = "serde-json")]
+mod serde_json;
+
 pub mod tasklet;
+
+pub mod undo;
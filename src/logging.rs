@@ -0,0 +1,162 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{fmt, ops::Deref};
+
+use crate::{OrphanedSubscriberError, Subscriber};
+
+impl<T> Subscriber<T> {
+    /// Print all received changes to `stderr`.
+    ///
+    /// Intended for debugging. Each change is printed with the given `label`
+    /// after it has been received by [`LoggingSubscriber::changed()`].
+    #[must_use]
+    pub const fn log_changes(self, label: &'static str) -> LoggingSubscriber<T>
+    where
+        T: fmt::Debug,
+    {
+        LoggingSubscriber {
+            subscriber: self,
+            label,
+        }
+    }
+}
+
+/// Subscriber that prints all received changes to `stderr`.
+///
+/// Created by [`Subscriber::log_changes()`].
+#[allow(missing_debug_implementations)]
+pub struct LoggingSubscriber<T> {
+    subscriber: Subscriber<T>,
+    label: &'static str,
+}
+
+impl<T> LoggingSubscriber<T>
+where
+    T: fmt::Debug,
+{
+    /// Receive change notifications and print the changed value.
+    ///
+    /// See also: [`Subscriber::changed()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(OrphanedSubscriberError)` if the subscriber is disconnected from the publisher.
+    pub async fn changed(&mut self) -> Result<(), OrphanedSubscriberError> {
+        self.subscriber.changed().await?;
+        log_change(self.label, &self.subscriber.read());
+        Ok(())
+    }
+
+    /// Read and acknowledge the next, changed value and print it.
+    ///
+    /// See also: [`Subscriber::read_changed()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(OrphanedSubscriberError)` if the subscriber is disconnected from the publisher.
+    pub async fn read_changed(
+        &mut self,
+    ) -> Result<impl Deref<Target = T> + '_, OrphanedSubscriberError> {
+        let next_changed = self.subscriber.read_changed().await?;
+        log_change(self.label, &next_changed);
+        Ok(next_changed)
+    }
+
+    /// Capture the next, changed value and print it.
+    ///
+    /// See also: [`Subscriber::map_changed()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(OrphanedSubscriberError)` if the subscriber is disconnected from the publisher.
+    pub async fn map_changed<U>(
+        &mut self,
+        mut map_fn: impl FnMut(&T) -> U,
+    ) -> Result<U, OrphanedSubscriberError> {
+        let label = self.label;
+        self.subscriber
+            .map_changed(|value| {
+                log_change(label, value);
+                map_fn(value)
+            })
+            .await
+    }
+
+    /// Capture the next, changed value conditionally and print it.
+    ///
+    /// All received changes are printed, including those that are filtered out.
+    ///
+    /// See also: [`Subscriber::filter_map_changed()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(OrphanedSubscriberError)` if the subscriber is disconnected from the publisher.
+    pub async fn filter_map_changed<U>(
+        &mut self,
+        mut filter_map_fn: impl FnMut(&T) -> Option<U>,
+    ) -> Result<U, OrphanedSubscriberError> {
+        let label = self.label;
+        self.subscriber
+            .filter_map_changed(|value| {
+                log_change(label, value);
+                filter_map_fn(value)
+            })
+            .await
+    }
+
+    /// Mark the current value as _changed_.
+    ///
+    /// See also: [`Subscriber::mark_changed()`]
+    pub fn mark_changed(&mut self) {
+        self.subscriber.mark_changed();
+    }
+
+    /// Stop logging and unwrap the subscriber.
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // Cannot be const for all back-ends.
+    pub fn into_inner(self) -> Subscriber<T> {
+        let Self { subscriber, .. } = self;
+        subscriber
+    }
+}
+
+fn log_change<T: fmt::Debug>(label: &str, value: &T) {
+    eprintln!("[discro/{label}] changed: {value:?}");
+}
+
+impl<T> Deref for LoggingSubscriber<T> {
+    type Target = Subscriber<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.subscriber
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use crate::Publisher;
+
+    #[tokio::test]
+    async fn forward_changes() {
+        let tx = Publisher::new(0);
+        let mut rx = tx.subscribe().log_changes("test");
+        tx.write(1);
+        rx.changed().await.unwrap();
+        assert_eq!(1, *rx.read());
+        tx.write(2);
+        assert_eq!(2, *rx.read_changed().await.unwrap());
+        tx.write(3);
+        assert_eq!(6, rx.map_changed(|value| value * 2).await.unwrap());
+        tx.write(4);
+        tx.write(5);
+        assert_eq!(
+            5,
+            rx.filter_map_changed(|value| (value % 2 == 1).then_some(*value))
+                .await
+                .unwrap()
+        );
+        rx.mark_changed();
+        assert!(rx.is_ready());
+    }
+}
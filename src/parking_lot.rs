@@ -0,0 +1,490 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Synchronous back-end based on [`parking_lot`] primitives.
+//!
+//! Intended for applications with a synchronous event loop that
+//! don't need to depend on an async runtime. Waiting for changes
+//! blocks the current thread.
+
+use std::{ops::Deref, sync::Arc};
+
+use parking_lot::{Condvar, Mutex, RwLock, RwLockReadGuard};
+
+use crate::OrphanedSubscriberError;
+
+#[cfg(feature = "tokio")]
+impl<T> crate::Publisher<T> {
+    /// Convert into a synchronous publisher.
+    ///
+    /// The new publisher is initialized with a clone of the current value.
+    /// Existing observers and subscribers are not transferred and become
+    /// orphaned after all clones of this publisher have been dropped.
+    #[must_use]
+    pub fn into_parking_lot_publisher(self) -> ParkingLotPublisher<T>
+    where
+        T: Clone,
+    {
+        ParkingLotPublisher::new(self.read().clone())
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    /// Incremented on every change notification.
+    version: u64,
+
+    /// Number of publishers and observers.
+    senders: usize,
+
+    subscribers: usize,
+}
+
+#[derive(Debug)]
+struct Shared<T> {
+    value: RwLock<T>,
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+impl<T> Shared<T> {
+    fn notify_changed(&self) {
+        let mut state = self.state.lock();
+        state.version = state.version.wrapping_add(1);
+        drop(state);
+        self.changed.notify_all();
+    }
+
+    fn add_sender(self: &Arc<Self>) -> Arc<Self> {
+        self.state.lock().senders += 1;
+        Arc::clone(self)
+    }
+
+    fn remove_sender(&self) {
+        let mut state = self.state.lock();
+        state.senders -= 1;
+        let orphaned = state.senders == 0;
+        drop(state);
+        if orphaned {
+            // Wake up all blocked subscribers.
+            self.changed.notify_all();
+        }
+    }
+
+    fn subscribe(self: &Arc<Self>, changed: bool) -> ParkingLotSubscriber<T> {
+        let mut state = self.state.lock();
+        state.subscribers += 1;
+        let seen_version = (!changed).then_some(state.version);
+        drop(state);
+        ParkingLotSubscriber {
+            shared: Arc::clone(self),
+            seen_version,
+        }
+    }
+}
+
+/// Read-locked reference to a shared value.
+#[derive(Debug)]
+pub struct ParkingLotRef<'r, T>(RwLockReadGuard<'r, T>);
+
+impl<T> Deref for ParkingLotRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Synchronous counterpart of [`Publisher`](crate::Publisher).
+#[derive(Debug)]
+pub struct ParkingLotPublisher<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> ParkingLotPublisher<T> {
+    #[must_use]
+    pub fn new(initial_value: T) -> Self {
+        let shared = Shared {
+            value: RwLock::new(initial_value),
+            state: Mutex::new(State {
+                version: 0,
+                senders: 1,
+                subscribers: 0,
+            }),
+            changed: Condvar::new(),
+        };
+        Self {
+            shared: Arc::new(shared),
+        }
+    }
+
+    #[must_use]
+    pub fn observe(&self) -> ParkingLotObserver<T> {
+        ParkingLotObserver {
+            shared: self.shared.add_sender(),
+        }
+    }
+
+    #[must_use]
+    pub fn has_subscribers(&self) -> bool {
+        self.shared.state.lock().subscribers > 0
+    }
+
+    #[must_use]
+    pub fn subscribe(&self) -> ParkingLotSubscriber<T> {
+        self.shared.subscribe(false)
+    }
+
+    #[must_use]
+    pub fn subscribe_changed(&self) -> ParkingLotSubscriber<T> {
+        self.shared.subscribe(true)
+    }
+
+    #[must_use]
+    pub fn read(&self) -> ParkingLotRef<'_, T> {
+        ParkingLotRef(self.shared.value.read())
+    }
+
+    pub fn write(&self, new_value: T) {
+        *self.shared.value.write() = new_value;
+        self.shared.notify_changed();
+    }
+
+    #[must_use]
+    pub fn replace(&self, new_value: T) -> T {
+        let old_value = std::mem::replace(&mut *self.shared.value.write(), new_value);
+        self.shared.notify_changed();
+        old_value
+    }
+
+    pub fn modify<M>(&self, modify: M) -> bool
+    where
+        M: FnOnce(&mut T) -> bool,
+    {
+        let modified = modify(&mut self.shared.value.write());
+        if modified {
+            self.shared.notify_changed();
+        }
+        modified
+    }
+
+    pub fn set_modified(&self) {
+        self.shared.notify_changed();
+    }
+}
+
+impl<T> Clone for ParkingLotPublisher<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.add_sender(),
+        }
+    }
+}
+
+impl<T> Default for ParkingLotPublisher<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl<T> Drop for ParkingLotPublisher<T> {
+    fn drop(&mut self) {
+        self.shared.remove_sender();
+    }
+}
+
+/// Synchronous counterpart of [`Observer`](crate::Observer).
+#[derive(Debug)]
+pub struct ParkingLotObserver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> ParkingLotObserver<T> {
+    #[must_use]
+    pub fn subscribe(&self) -> ParkingLotSubscriber<T> {
+        self.shared.subscribe(false)
+    }
+
+    #[must_use]
+    pub fn subscribe_changed(&self) -> ParkingLotSubscriber<T> {
+        self.shared.subscribe(true)
+    }
+
+    #[must_use]
+    pub fn read(&self) -> ParkingLotRef<'_, T> {
+        ParkingLotRef(self.shared.value.read())
+    }
+}
+
+impl<T> Clone for ParkingLotObserver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.add_sender(),
+        }
+    }
+}
+
+impl<T> Drop for ParkingLotObserver<T> {
+    fn drop(&mut self) {
+        self.shared.remove_sender();
+    }
+}
+
+/// Synchronous counterpart of [`Subscriber`](crate::Subscriber).
+#[derive(Debug)]
+pub struct ParkingLotSubscriber<T> {
+    shared: Arc<Shared<T>>,
+
+    /// `None` if marked as changed.
+    seen_version: Option<u64>,
+}
+
+impl<T> ParkingLotSubscriber<T> {
+    #[must_use]
+    pub fn read(&self) -> ParkingLotRef<'_, T> {
+        ParkingLotRef(self.shared.value.read())
+    }
+
+    #[must_use]
+    pub fn read_ack(&mut self) -> ParkingLotRef<'_, T> {
+        // Acknowledge the version before reading the value to not miss
+        // any changes that happen in between.
+        self.seen_version = Some(self.shared.state.lock().version);
+        ParkingLotRef(self.shared.value.read())
+    }
+
+    pub fn mark_changed(&mut self) {
+        self.seen_version = None;
+    }
+
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.seen_version != Some(self.shared.state.lock().version)
+    }
+
+    /// Block the current thread until the value has changed.
+    ///
+    /// Returns immediately if the subscriber has been marked as changed
+    /// or if the current value has not been seen yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(OrphanedSubscriberError)` if all publishers and observers
+    /// have been dropped and no unseen changes are pending.
+    pub fn changed(&mut self) -> Result<(), OrphanedSubscriberError> {
+        let mut state = self.shared.state.lock();
+        loop {
+            if self.seen_version != Some(state.version) {
+                self.seen_version = Some(state.version);
+                return Ok(());
+            }
+            if state.senders == 0 {
+                return Err(OrphanedSubscriberError);
+            }
+            self.shared.changed.wait(&mut state);
+        }
+    }
+
+    /// Block the current thread until the value has changed and read it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ParkingLotSubscriber::changed()`].
+    pub fn read_changed(&mut self) -> Result<ParkingLotRef<'_, T>, OrphanedSubscriberError> {
+        self.changed()?;
+        Ok(self.read())
+    }
+}
+
+impl<T> Clone for ParkingLotSubscriber<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().subscribers += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+            seen_version: self.seen_version,
+        }
+    }
+}
+
+impl<T> Drop for ParkingLotSubscriber<T> {
+    fn drop(&mut self) {
+        self.shared.state.lock().subscribers -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn changed_blocks_until_written() {
+        let publisher = ParkingLotPublisher::new(0);
+        let mut subscriber = publisher.subscribe();
+        assert!(!subscriber.is_ready());
+
+        let writer = thread::spawn(move || {
+            publisher.write(1);
+            publisher
+        });
+        assert_eq!(1, *subscriber.read_changed().unwrap());
+        assert!(!subscriber.is_ready());
+
+        let publisher = writer.join().unwrap();
+        assert!(publisher.has_subscribers());
+        drop(subscriber);
+        assert!(!publisher.has_subscribers());
+    }
+
+    #[test]
+    fn orphaned_after_publishers_and_observers_dropped() {
+        let publisher = ParkingLotPublisher::new(0);
+        let observer = publisher.observe();
+        let mut subscriber = observer.subscribe();
+
+        publisher.write(1);
+        drop(publisher);
+        // The pending change is still delivered.
+        assert!(subscriber.changed().is_ok());
+
+        thread::spawn(move || drop(observer)).join().unwrap();
+        assert!(subscriber.changed().is_err());
+        assert_eq!(1, *subscriber.read());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn into_parking_lot_publisher() {
+        let publisher = crate::Publisher::new(1).into_parking_lot_publisher();
+        assert_eq!(1, *publisher.read());
+    }
+
+    #[test]
+    fn subscribe_changed() {
+        let publisher = ParkingLotPublisher::new(0);
+        let mut subscriber = publisher.subscribe_changed();
+        assert!(subscriber.is_ready());
+        assert_eq!(0, *subscriber.read_ack());
+        assert!(!subscriber.is_ready());
+        assert!(!publisher.modify(|_| false));
+        assert!(!subscriber.is_ready());
+        subscriber.mark_changed();
+        assert!(subscriber.is_ready());
+    }
+}
+
+#[cfg(test)]
+mod traits {
+    use super::{ParkingLotObserver, ParkingLotPublisher, ParkingLotRef, ParkingLotSubscriber};
+
+    // <https://github.com/rust-lang/api-guidelines/issues/223#issuecomment-683346783>
+    const _: () = {
+        const fn assert_send<T: Send>() {}
+        let _ = assert_send::<ParkingLotPublisher<i32>>;
+    };
+
+    // <https://github.com/rust-lang/api-guidelines/issues/223#issuecomment-683346783>
+    const _: () = {
+        const fn assert_sync<T: Sync>() {}
+        let _ = assert_sync::<ParkingLotPublisher<i32>>;
+    };
+
+    impl<T> crate::traits::Ref<T> for ParkingLotRef<'_, T> {}
+
+    impl<'r, T> crate::traits::Readable<'r, T, ParkingLotRef<'r, T>> for ParkingLotPublisher<T> {
+        fn read(&self) -> ParkingLotRef<'_, T> {
+            self.read()
+        }
+    }
+
+    impl<'r, T> crate::traits::Readable<'r, T, ParkingLotRef<'r, T>> for ParkingLotObserver<T> {
+        fn read(&self) -> ParkingLotRef<'_, T> {
+            self.read()
+        }
+    }
+
+    impl<'r, T> crate::traits::Readable<'r, T, ParkingLotRef<'r, T>> for ParkingLotSubscriber<T> {
+        fn read(&self) -> ParkingLotRef<'_, T> {
+            self.read()
+        }
+    }
+
+    impl<'r, T> crate::traits::Subscribable<'r, T, ParkingLotRef<'r, T>, ParkingLotSubscriber<T>>
+        for ParkingLotPublisher<T>
+    {
+        fn subscribe(&self) -> ParkingLotSubscriber<T> {
+            self.subscribe()
+        }
+
+        fn subscribe_changed(&self) -> ParkingLotSubscriber<T> {
+            self.subscribe_changed()
+        }
+    }
+
+    impl<'r, T> crate::traits::Subscribable<'r, T, ParkingLotRef<'r, T>, ParkingLotSubscriber<T>>
+        for ParkingLotObserver<T>
+    {
+        fn subscribe(&self) -> ParkingLotSubscriber<T> {
+            self.subscribe()
+        }
+
+        fn subscribe_changed(&self) -> ParkingLotSubscriber<T> {
+            self.subscribe_changed()
+        }
+    }
+
+    impl<'r, T>
+        crate::traits::Publisher<
+            'r,
+            T,
+            ParkingLotRef<'r, T>,
+            ParkingLotObserver<T>,
+            ParkingLotSubscriber<T>,
+        > for ParkingLotPublisher<T>
+    {
+        fn observe(&self) -> ParkingLotObserver<T> {
+            self.observe()
+        }
+
+        fn has_subscribers(&self) -> bool {
+            self.has_subscribers()
+        }
+
+        fn write(&self, new_value: T) {
+            self.write(new_value);
+        }
+
+        fn replace(&self, new_value: T) -> T {
+            self.replace(new_value)
+        }
+
+        fn modify<M>(&self, modify: M) -> bool
+        where
+            M: FnOnce(&mut T) -> bool,
+        {
+            self.modify(modify)
+        }
+
+        fn set_modified(&self) {
+            self.set_modified();
+        }
+    }
+
+    impl<'r, T> crate::traits::Observer<'r, T, ParkingLotRef<'r, T>, ParkingLotSubscriber<T>>
+        for ParkingLotObserver<T>
+    {
+    }
+
+    impl<'r, T> crate::traits::Subscriber<'r, T, ParkingLotRef<'r, T>> for ParkingLotSubscriber<T> {
+        fn read_ack(&mut self) -> ParkingLotRef<'_, T> {
+            self.read_ack()
+        }
+    }
+
+    // `crate::traits::ChangeListener` is not implemented, because waiting
+    // for changes blocks the current thread instead of being async.
+}
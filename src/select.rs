@@ -0,0 +1,92 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Wait for the first change among multiple subscribers.
+
+use crate::OrphanedSubscriberError;
+
+/// The subscriber that has changed first.
+///
+/// Returned by [`select_changed!`](crate::select_changed). Each variant
+/// contains the result of `Subscriber::changed()` for the subscriber
+/// at the corresponding position.
+#[derive(Debug)]
+pub enum SelectChanged {
+    /// The first subscriber has changed
+    A(Result<(), OrphanedSubscriberError>),
+
+    /// The second subscriber has changed
+    B(Result<(), OrphanedSubscriberError>),
+
+    /// The third subscriber has changed
+    C(Result<(), OrphanedSubscriberError>),
+}
+
+/// Wait for the first change among 2 or 3 subscribers.
+///
+/// The subscribers may observe values of different types. Evaluates
+/// to a future that resolves into a [`SelectChanged`] variant for the
+/// subscriber that has changed first.
+///
+/// If multiple subscribers have changed, the one with the lowest position
+/// wins. All other subscribers are not affected, i.e. their changes are
+/// still pending.
+#[macro_export]
+macro_rules! select_changed {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::__select_changed!(a => A: $a, b => B: $b)
+    };
+    ($a:expr, $b:expr, $c:expr $(,)?) => {
+        $crate::__select_changed!(a => A: $a, b => B: $b, c => C: $c)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __select_changed {
+    ($($changed:ident => $variant:ident: $subscriber:expr),+) => {
+        async {
+            $(
+                let mut $changed = ::core::pin::pin!($subscriber.changed());
+            )+
+            ::core::future::poll_fn(|cx| {
+                $(
+                    if let ::core::task::Poll::Ready(result) =
+                        ::core::future::Future::poll($changed.as_mut(), cx)
+                    {
+                        return ::core::task::Poll::Ready($crate::SelectChanged::$variant(result));
+                    }
+                )+
+                ::core::task::Poll::Pending
+            })
+            .await
+        }
+    };
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use super::SelectChanged;
+    use crate::Publisher;
+
+    #[tokio::test]
+    async fn select_changed_subscribers_of_different_types() {
+        let tx_a = Publisher::new(0);
+        let tx_b = Publisher::new(String::new());
+        let tx_c = Publisher::new(false);
+        let mut rx_a = tx_a.subscribe();
+        let mut rx_b = tx_b.subscribe();
+        let mut rx_c = tx_c.subscribe();
+        tx_b.write("changed".to_owned());
+        assert!(matches!(
+            select_changed!(rx_a, rx_b, rx_c).await,
+            SelectChanged::B(Ok(()))
+        ));
+        drop(tx_a);
+        assert!(matches!(
+            select_changed!(rx_a, rx_b).await,
+            SelectChanged::A(Err(_))
+        ));
+        assert!(!rx_c.is_ready());
+    }
+}
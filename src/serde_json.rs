@@ -0,0 +1,65 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Read and write shared values as JSON.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::Publisher;
+
+impl<T> Publisher<T> {
+    /// Serialize the current value as a JSON string.
+    ///
+    /// The value is read-locked during serialization.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, serde_json::Error>
+    where
+        T: Serialize,
+    {
+        serde_json::to_string(&*self.read())
+    }
+
+    /// Overwrite the current value with a value deserialized from a JSON string
+    /// and emit a change notification.
+    ///
+    /// The current value is not modified if deserialization fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deserialization fails.
+    pub fn set_from_json(&self, json: &str) -> Result<(), serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        let new_value: T = serde_json::from_str(json)?;
+        self.write(new_value);
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use crate::Publisher;
+
+    #[test]
+    fn json_round_trip() {
+        let tx = Publisher::new(vec![(1, "one".to_owned()), (2, "two".to_owned())]);
+        let json = tx.to_json().unwrap();
+        let rx = Publisher::<Vec<(i32, String)>>::default();
+        rx.set_from_json(&json).unwrap();
+        assert_eq!(*tx.read(), *rx.read());
+    }
+
+    #[test]
+    fn set_from_invalid_json() {
+        let tx = Publisher::new(1);
+        let mut rx = tx.subscribe();
+        assert!(tx.set_from_json("\"one\"").is_err());
+        assert_eq!(1, *tx.read());
+        assert!(!rx.is_ready());
+        assert_eq!(1, *rx.read_ack());
+    }
+}
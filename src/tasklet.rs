@@ -25,9 +25,17 @@ pub enum OnChanged {
 /// No `_async` variant of this function could be provided, because
 /// holding locks across yield points is not permitted.
 pub async fn observe_changes<T>(
+    subscriber: Subscriber<T>,
+    on_changed_fn: impl FnMut(&T) -> OnChanged,
+) {
+    observe_changes_until_aborted(subscriber, on_changed_fn).await;
+}
+
+/// Returns `true` if aborted by the consumer or `false` if the publisher has disappeared.
+async fn observe_changes_until_aborted<T>(
     mut subscriber: Subscriber<T>,
     mut on_changed_fn: impl FnMut(&T) -> OnChanged,
-) {
+) -> bool {
     while let Ok(next_changed_ref) = subscriber.read_changed().await {
         let result =
             panic::catch_unwind(panic::AssertUnwindSafe(|| on_changed_fn(&next_changed_ref)));
@@ -41,7 +49,7 @@ pub async fn observe_changes<T>(
                 }
                 OnChanged::Abort => {
                     // Aborted by the consumer.
-                    break;
+                    return true;
                 }
             },
             Err(panicked) => {
@@ -52,6 +60,71 @@ pub async fn observe_changes<T>(
         // Unreachable
     }
     // Publisher has disappeared.
+    false
+}
+
+/// Reason for finishing to observe changes.
+#[cfg(all(feature = "tokio-signal", unix))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownReason {
+    /// A signal has been received
+    Signal,
+
+    /// The publisher has disappeared
+    PublisherDropped,
+
+    /// Aborted by the consumer
+    Aborted,
+}
+
+/// Observe a shared value until a signal is received.
+///
+/// Same as [`observe_changes()`] with the only difference that observing
+/// changes finishes after receiving a signal, e.g. `SIGTERM` or `SIGHUP`.
+///
+/// Returns the reason for finishing.
+#[cfg(all(feature = "tokio-signal", unix))]
+pub async fn observe_changes_with_signal<T>(
+    subscriber: Subscriber<T>,
+    mut signal: tokio::signal::unix::Signal,
+    on_changed_fn: impl FnMut(&T) -> OnChanged,
+) -> ShutdownReason {
+    let mut observe_changes =
+        std::pin::pin!(observe_changes_until_aborted(subscriber, on_changed_fn));
+    std::future::poll_fn(|cx| {
+        if let std::task::Poll::Ready(Some(())) = signal.poll_recv(cx) {
+            return std::task::Poll::Ready(ShutdownReason::Signal);
+        }
+        observe_changes.as_mut().poll(cx).map(|aborted| {
+            if aborted {
+                ShutdownReason::Aborted
+            } else {
+                ShutdownReason::PublisherDropped
+            }
+        })
+    })
+    .await
+}
+
+/// Observe a shared value and count the changes.
+///
+/// Same as [`observe_changes()`] with the only difference that the
+/// `on_changed_fn` closure also receives the epoch of the change.
+///
+/// The epoch starts at 0 and is incremented for every observed change.
+/// Multiple writes that happen before the next change is observed
+/// only count as a single change.
+pub async fn observe_changes_with_epoch<T>(
+    subscriber: Subscriber<T>,
+    mut on_changed_fn: impl FnMut(u64, &T) -> OnChanged,
+) {
+    let mut epoch = 0;
+    observe_changes(subscriber, move |value| {
+        let on_changed = on_changed_fn(epoch, value);
+        epoch += 1;
+        on_changed
+    })
+    .await;
 }
 
 /// Capture changes while observing a shared value.
@@ -169,3 +242,117 @@ where
         }
     }
 }
+
+/// Capture changes asynchronously and transform the captured values.
+///
+/// Same as [`capture_changes_async()`] with the only difference that
+/// each captured value is cloned and passed through all `middleware`
+/// transformations in order before invoking `on_changed_value_fn`.
+///
+/// The captured value itself is not affected by the transformations.
+pub fn capture_changes_with_middleware<'a, S, T, M, F>(
+    subscriber: Subscriber<S>,
+    initial_value: T,
+    capture_changed_value_fn: impl FnMut(&mut T, &S) -> bool + Send + 'a,
+    middleware: Vec<M>,
+    mut on_changed_value_fn: impl FnMut(&T) -> F + Send + 'a,
+) -> impl Future<Output = ()> + Send + 'a
+where
+    S: Send + Sync + 'a,
+    T: Clone + Send + 'a,
+    M: Fn(T) -> T + Send + 'a,
+    F: Future<Output = OnChanged> + Send + 'a,
+{
+    capture_changes_async(
+        subscriber,
+        initial_value,
+        capture_changed_value_fn,
+        move |value: &T| {
+            let value = middleware
+                .iter()
+                .fold(value.clone(), |value, transform| transform(value));
+            on_changed_value_fn(&value)
+        },
+    )
+}
+
+/// Capture changes while observing a shared value and send them into a channel.
+///
+/// Same as [`capture_changes_async()`] with the only difference that each
+/// captured value is cloned and sent into `sender`.
+///
+/// Stops when either the publisher or the receiver of the channel has
+/// disappeared, even while waiting for the next change.
+#[cfg(feature = "tokio")]
+pub fn capture_into_mpsc<'a, S, T>(
+    subscriber: Subscriber<S>,
+    initial_value: T,
+    capture_changed_value_fn: impl FnMut(&mut T, &S) -> bool + Send + 'a,
+    sender: tokio::sync::mpsc::Sender<T>,
+) -> impl Future<Output = ()> + Send + 'a
+where
+    S: Send + Sync + 'a,
+    T: Clone + Send + 'a,
+{
+    let closed_sender = sender.clone();
+    let capture_changes = capture_changes_async(
+        subscriber,
+        initial_value,
+        capture_changed_value_fn,
+        move |value: &T| {
+            let sender = sender.clone();
+            let value = value.clone();
+            async move {
+                if sender.send(value).await.is_ok() {
+                    OnChanged::Continue
+                } else {
+                    // Aborted after the receiver disappeared.
+                    OnChanged::Abort
+                }
+            }
+        },
+    );
+    async move {
+        let mut capture_changes = std::pin::pin!(capture_changes);
+        let mut closed = std::pin::pin!(closed_sender.closed());
+        std::future::poll_fn(|cx| {
+            if closed.as_mut().poll(cx).is_ready() {
+                // Aborted after the receiver disappeared.
+                return std::task::Poll::Ready(());
+            }
+            capture_changes.as_mut().poll(cx)
+        })
+        .await;
+    }
+}
+
+/// Spawn a task that captures changes asynchronously.
+///
+/// Spawns [`capture_changes_async()`] and returns both the join handle
+/// and an abort handle of the spawned task. The abort handle allows to
+/// cancel the task without giving up the join handle.
+///
+/// # Panics
+///
+/// Panics if not called from within a Tokio runtime.
+#[cfg(feature = "tokio-rt")]
+pub fn spawn_abortable_capture<S, T, F>(
+    subscriber: Subscriber<S>,
+    initial_value: T,
+    capture_changed_value_fn: impl FnMut(&mut T, &S) -> bool + Send + 'static,
+    on_changed_value_fn: impl FnMut(&T) -> F + Send + 'static,
+) -> (tokio::task::JoinHandle<()>, tokio::task::AbortHandle)
+where
+    S: Send + Sync + 'static,
+    T: Send + 'static,
+    F: Future<Output = OnChanged> + Send + 'static,
+{
+    let join_handle = tokio::spawn(capture_changes_async(
+        subscriber,
+        initial_value,
+        capture_changed_value_fn,
+        on_changed_value_fn,
+    ));
+    let abort_handle = join_handle.abort_handle();
+    (join_handle, abort_handle)
+}
@@ -0,0 +1,170 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{
+    ops::Deref,
+    time::{Duration, Instant},
+};
+
+use crate::{OrphanedSubscriberError, Subscriber};
+
+impl<T> Subscriber<T> {
+    /// Record the time when changes are received.
+    ///
+    /// Intended for lag monitoring. The time is recorded every time
+    /// [`TimedSubscriber::changed()`] returns successfully.
+    ///
+    /// Not supported on targets without a system clock, e.g.
+    /// `wasm32-unknown-unknown`, where obtaining the current time panics.
+    #[must_use]
+    pub const fn track_change_time(self) -> TimedSubscriber<T> {
+        TimedSubscriber {
+            subscriber: self,
+            last_changed_at: None,
+        }
+    }
+}
+
+/// Subscriber that records the time of the last received change.
+///
+/// Created by [`Subscriber::track_change_time()`].
+#[allow(missing_debug_implementations)]
+pub struct TimedSubscriber<T> {
+    subscriber: Subscriber<T>,
+    last_changed_at: Option<Instant>,
+}
+
+impl<T> TimedSubscriber<T> {
+    /// Receive change notifications and record the current time.
+    ///
+    /// See also: [`Subscriber::changed()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(OrphanedSubscriberError)` if the subscriber is disconnected from the publisher.
+    pub async fn changed(&mut self) -> Result<(), OrphanedSubscriberError> {
+        self.subscriber.changed().await?;
+        self.last_changed_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Read and acknowledge the next, changed value and record the current time.
+    ///
+    /// See also: [`Subscriber::read_changed()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(OrphanedSubscriberError)` if the subscriber is disconnected from the publisher.
+    pub async fn read_changed(
+        &mut self,
+    ) -> Result<impl Deref<Target = T> + '_, OrphanedSubscriberError> {
+        let next_changed = self.subscriber.read_changed().await?;
+        self.last_changed_at = Some(Instant::now());
+        Ok(next_changed)
+    }
+
+    /// Capture the next, changed value and record the current time.
+    ///
+    /// See also: [`Subscriber::map_changed()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(OrphanedSubscriberError)` if the subscriber is disconnected from the publisher.
+    pub async fn map_changed<U>(
+        &mut self,
+        map_fn: impl FnMut(&T) -> U,
+    ) -> Result<U, OrphanedSubscriberError> {
+        let next_item = self.subscriber.map_changed(map_fn).await?;
+        self.last_changed_at = Some(Instant::now());
+        Ok(next_item)
+    }
+
+    /// Capture the next, changed value conditionally and record the current time.
+    ///
+    /// Only the time of the accepted change is recorded.
+    ///
+    /// See also: [`Subscriber::filter_map_changed()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(OrphanedSubscriberError)` if the subscriber is disconnected from the publisher.
+    pub async fn filter_map_changed<U>(
+        &mut self,
+        filter_map_fn: impl FnMut(&T) -> Option<U>,
+    ) -> Result<U, OrphanedSubscriberError> {
+        let next_item = self.subscriber.filter_map_changed(filter_map_fn).await?;
+        self.last_changed_at = Some(Instant::now());
+        Ok(next_item)
+    }
+
+    /// Elapsed time since the last change has been received.
+    ///
+    /// Returns the duration since a change has been received for the
+    /// last time, or `None` if no change has been received yet.
+    #[must_use]
+    pub fn time_since_last_change(&self) -> Option<Duration> {
+        self.last_changed_at.as_ref().map(Instant::elapsed)
+    }
+
+    /// Mark the current value as _changed_.
+    ///
+    /// See also: [`Subscriber::mark_changed()`]
+    pub fn mark_changed(&mut self) {
+        self.subscriber.mark_changed();
+    }
+
+    /// Stop recording and unwrap the subscriber.
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // Cannot be const for all back-ends.
+    pub fn into_inner(self) -> Subscriber<T> {
+        let Self { subscriber, .. } = self;
+        subscriber
+    }
+}
+
+impl<T> Deref for TimedSubscriber<T> {
+    type Target = Subscriber<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.subscriber
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use crate::Publisher;
+
+    #[tokio::test]
+    async fn record_time_of_last_change() {
+        let tx = Publisher::new(0);
+        let mut rx = tx.subscribe().track_change_time();
+        assert!(rx.time_since_last_change().is_none());
+        // Marking as changed does not count as a received change.
+        rx.mark_changed();
+        assert!(rx.time_since_last_change().is_none());
+        rx.changed().await.unwrap();
+        let first = rx.time_since_last_change().unwrap();
+        let second = rx.time_since_last_change().unwrap();
+        assert!(second >= first);
+    }
+
+    #[tokio::test]
+    async fn record_time_of_captured_changes() {
+        let tx = Publisher::new(0);
+        let mut rx = tx.subscribe().track_change_time();
+        tx.write(1);
+        assert_eq!(1, *rx.read_changed().await.unwrap());
+        assert!(rx.time_since_last_change().is_some());
+        let mut rx = rx.into_inner().track_change_time();
+        tx.write(2);
+        assert_eq!(4, rx.map_changed(|value| value * 2).await.unwrap());
+        assert!(rx.time_since_last_change().is_some());
+        let mut rx = rx.into_inner().track_change_time();
+        tx.write(3);
+        assert_eq!(
+            3,
+            rx.filter_map_changed(|value| Some(*value)).await.unwrap()
+        );
+        assert!(rx.time_since_last_change().is_some());
+    }
+}
@@ -8,11 +8,18 @@
 #![allow(missing_docs)]
 #![allow(clippy::missing_errors_doc)]
 
-use std::ops::Deref;
+use std::{
+    fmt,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, OnceLock, PoisonError, Weak,
+    },
+};
 
 use tokio::sync::watch;
 
-use super::OrphanedSubscriberError;
+use super::{OnceSentinel, OrphanedSubscriberError, WriteError};
 use crate::subscriber::{filter_map_changed, map_changed};
 
 #[derive(Debug)]
@@ -26,34 +33,242 @@ impl<T> Deref for Ref<'_, T> {
     }
 }
 
+type Callback<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+struct CallbackRegistry<T> {
+    next_id: usize,
+    /// Immutable snapshot that is replaced when (un-)registering callbacks.
+    registered: Arc<[(usize, Callback<T>)]>,
+}
+
+struct Callbacks<T> {
+    /// Number of registered callbacks.
+    ///
+    /// Allows to skip locking the registry if no callbacks are registered.
+    count: AtomicUsize,
+    registry: Mutex<CallbackRegistry<T>>,
+}
+
+impl<T> Callbacks<T> {
+    fn new() -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            registry: Mutex::new(CallbackRegistry {
+                next_id: 0,
+                registered: Arc::new([]),
+            }),
+        }
+    }
+
+    fn update_registered(
+        &self,
+        update: impl FnOnce(&mut CallbackRegistry<T>) -> Arc<[(usize, Callback<T>)]>,
+    ) {
+        let mut registry = self.registry.lock().unwrap_or_else(PoisonError::into_inner);
+        let registered = update(&mut registry);
+        self.count.store(registered.len(), Ordering::Release);
+        registry.registered = registered;
+    }
+
+    fn register(&self, callback: Callback<T>) -> usize {
+        let mut id = 0;
+        self.update_registered(|registry| {
+            id = registry.next_id;
+            registry.next_id += 1;
+            registry
+                .registered
+                .iter()
+                .map(|(id, callback)| (*id, Arc::clone(callback)))
+                .chain(std::iter::once((id, callback)))
+                .collect()
+        });
+        id
+    }
+
+    fn invoke(&self, value: &T) {
+        if self.count.load(Ordering::Acquire) == 0 {
+            return;
+        }
+        // Invoke the callbacks on a snapshot without holding the lock.
+        // This allows callbacks to (un-)register callbacks.
+        let registered = {
+            let registry = self.registry.lock().unwrap_or_else(PoisonError::into_inner);
+            Arc::clone(&registry.registered)
+        };
+        for (_, callback) in registered.iter() {
+            callback(value);
+        }
+    }
+}
+
+impl<T> fmt::Debug for Callbacks<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Callbacks")
+            .field("count", &self.count)
+            .finish_non_exhaustive()
+    }
+}
+
+trait UnregisterCallback: Send + Sync {
+    fn unregister(&self, id: usize);
+}
+
+impl<T> UnregisterCallback for Callbacks<T> {
+    fn unregister(&self, id: usize) {
+        self.update_registered(|registry| {
+            registry
+                .registered
+                .iter()
+                .filter(|(registered_id, _)| *registered_id != id)
+                .map(|(id, callback)| (*id, Arc::clone(callback)))
+                .collect()
+        });
+    }
+}
+
+/// Unregisters the callback when dropped.
+pub struct CallbackHandle {
+    callbacks: Weak<dyn UnregisterCallback>,
+    id: usize,
+}
+
+impl fmt::Debug for CallbackHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallbackHandle")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for CallbackHandle {
+    fn drop(&mut self) {
+        if let Some(callbacks) = self.callbacks.upgrade() {
+            callbacks.unregister(self.id);
+        }
+    }
+}
+
+/// Saturation flags of all subscribers of a channel.
+#[derive(Debug, Default)]
+struct Saturations {
+    flags: Mutex<Vec<Weak<AtomicBool>>>,
+}
+
+impl Saturations {
+    fn register(&self) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut flags = self.flags.lock().unwrap_or_else(PoisonError::into_inner);
+        // Discard the flags of dropped subscribers.
+        flags.retain(|flag| flag.strong_count() > 0);
+        flags.push(Arc::downgrade(&flag));
+        flag
+    }
+
+    fn is_saturated(&self) -> bool {
+        let flags = self.flags.lock().unwrap_or_else(PoisonError::into_inner);
+        flags
+            .iter()
+            .filter_map(Weak::upgrade)
+            .any(|flag| flag.load(Ordering::Relaxed))
+    }
+}
+
+/// Channel state that is shared by all instances of a publisher.
 #[derive(Debug)]
-pub struct Publisher<T> {
+struct Shared<T> {
     tx: watch::Sender<T>,
+    callbacks: Arc<Callbacks<T>>,
+    cycle_counter: AtomicUsize,
+    saturations: Arc<Saturations>,
+    /// Created on first use, because every publisher would otherwise
+    /// recursively own another one.
+    errors: OnceLock<Publisher<Option<WriteError>>>,
+    /// Keeps a derived publisher connected to its source until dropped.
+    _derived_from: Option<CallbackHandle>,
+}
+
+impl<T> Shared<T> {
+    fn new(initial_value: T, derived_from: Option<CallbackHandle>) -> Self {
+        Self {
+            tx: watch::channel(initial_value).0,
+            callbacks: Arc::new(Callbacks::new()),
+            cycle_counter: AtomicUsize::new(0),
+            saturations: Arc::default(),
+            errors: OnceLock::new(),
+            _derived_from: derived_from,
+        }
+    }
+
+    fn write(&self, new_value: T) {
+        // Sender::send() would prematurely abort and fail if
+        // no senders are connected and the current value would
+        // not be replaced as expected. Therefore we have to use
+        // Sender::send_modify() here!
+        // The conversion into the value is done before the locking scope.
+        self.tx.send_modify(move |value| {
+            *value = new_value;
+            self.callbacks.invoke(value);
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct Publisher<T> {
+    shared: Arc<Shared<T>>,
 }
 
 impl<T> Publisher<T> {
     #[must_use]
     pub fn new(initial_value: T) -> Self {
         Self {
-            tx: watch::channel(initial_value).0,
+            shared: Arc::new(Shared::new(initial_value, None)),
         }
     }
 
+    #[must_use]
+    pub fn new_with_hook(initial_value: T, hook: impl Fn(&T) + Send + Sync + 'static) -> Self {
+        let publisher = Self::new(initial_value);
+        publisher.register_callback(Arc::new(hook));
+        publisher
+    }
+
+    #[must_use]
+    pub fn new_derived<U>(source: &Observer<U>, derive: impl FnOnce(&U) -> T) -> Self {
+        Self::new(derive(&source.read()))
+    }
+
     #[must_use]
     pub fn observe(&self) -> Observer<T> {
         Observer {
-            tx: self.tx.clone(),
+            tx: self.shared.tx.clone(),
+            saturations: Arc::clone(&self.shared.saturations),
         }
     }
 
+    #[must_use]
+    pub fn observe_and_read(&self) -> (Observer<T>, Ref<'_, T>) {
+        let observer = self.observe();
+        let value = self.read();
+        (observer, value)
+    }
+
     #[must_use]
     pub fn has_subscribers(&self) -> bool {
-        !self.tx.is_closed()
+        !self.shared.tx.is_closed()
+    }
+
+    #[must_use]
+    pub fn error_channel(&self) -> Observer<Option<WriteError>> {
+        // Writing is infallible and the value always remains `None`.
+        self.shared
+            .errors
+            .get_or_init(|| Publisher::new(None))
+            .observe()
     }
 
     #[must_use]
     pub fn subscribe(&self) -> Subscriber<T> {
-        Subscriber::new(self.tx.subscribe())
+        Subscriber::new(self.shared.tx.subscribe(), &self.shared.saturations)
     }
 
     #[must_use]
@@ -65,39 +280,327 @@ impl<T> Publisher<T> {
 
     #[must_use]
     pub fn read(&self) -> Ref<'_, T> {
-        Ref(self.tx.borrow())
+        Ref(self.shared.tx.borrow())
     }
 
     pub fn write(&self, new_value: T) {
-        // Sender::send() would prematurely abort and fail if
-        // no senders are connected and the current value would
-        // not be replaced as expected. Therefore we have to use
-        // Sender::send_modify() here!
-        // The conversion into the value is done before the locking scope.
-        self.tx.send_modify(move |value| *value = new_value);
+        self.shared.write(new_value);
+    }
+
+    /// Same as [`write()`](Self::write), but logs write errors instead of failing.
+    ///
+    /// Writing is infallible with this back-end, i.e. no errors are
+    /// ever logged.
+    #[cfg(feature = "tracing")]
+    pub fn write_or_log(&self, new_value: T) {
+        self.write(new_value);
+    }
+
+    pub fn write_if_unsaturated(&self, new_value: T) -> bool {
+        if self.shared.saturations.is_saturated() {
+            return false;
+        }
+        self.write(new_value);
+        true
+    }
+
+    pub fn write_lazy(&self, new_value: impl FnOnce() -> T) -> bool {
+        if !self.has_subscribers() {
+            return false;
+        }
+        self.write(new_value());
+        true
+    }
+
+    pub fn write_if_connected(&self, new_value: T) -> bool {
+        if !self.has_subscribers() {
+            return false;
+        }
+        self.write(new_value);
+        true
+    }
+
+    pub fn try_into_value(self) -> Result<T, Self>
+    where
+        T: Clone,
+    {
+        if self.has_subscribers() {
+            return Err(self);
+        }
+        // The value is shared with all observers and cannot be moved out.
+        Ok(T::clone(&self.read()))
+    }
+
+    pub fn write_once(self, new_value: T) -> OnceSentinel {
+        self.write(new_value);
+        OnceSentinel
     }
 
     #[must_use]
     pub fn replace(&self, new_value: T) -> T {
-        self.tx.send_replace(new_value)
+        let mut value = new_value;
+        self.shared.tx.send_modify(|current_value| {
+            std::mem::swap(current_value, &mut value);
+            self.invoke_callbacks(current_value);
+        });
+        value
+    }
+
+    #[must_use]
+    pub fn replace_map<F>(&self, map: F) -> T
+    where
+        F: FnOnce(T) -> T,
+        T: Clone,
+    {
+        let mut old_value = None;
+        self.modify(|value| {
+            let new_value = map(value.clone());
+            old_value = Some(std::mem::replace(value, new_value));
+            true
+        });
+        let Some(old_value) = old_value else {
+            unreachable!("modified");
+        };
+        old_value
     }
 
     pub fn modify<M>(&self, modify: M) -> bool
     where
         M: FnOnce(&mut T) -> bool,
     {
-        self.tx.send_if_modified(modify)
+        self.shared.tx.send_if_modified(|value| {
+            let modified = modify(value);
+            if modified {
+                self.invoke_callbacks(value);
+            }
+            modified
+        })
+    }
+
+    pub fn atomic_update<F>(&self, update: F)
+    where
+        F: Fn(&T) -> T,
+        T: Clone + PartialEq,
+    {
+        loop {
+            let current_value = T::clone(&self.read());
+            let new_value = update(&current_value);
+            let mut raced = false;
+            self.modify(|value| {
+                if *value != current_value {
+                    raced = true;
+                    return false;
+                }
+                if new_value == current_value {
+                    return false;
+                }
+                *value = new_value;
+                true
+            });
+            if !raced {
+                return;
+            }
+            // Retry after a concurrent write.
+        }
+    }
+
+    pub fn on_change_callback(
+        &self,
+        on_change: impl Fn(&T) + Send + Sync + 'static,
+    ) -> CallbackHandle
+    where
+        T: 'static,
+    {
+        let id = self.register_callback(Arc::new(on_change));
+        let callbacks = Arc::downgrade(&self.shared.callbacks);
+        CallbackHandle { callbacks, id }
+    }
+
+    fn register_callback(&self, callback: Callback<T>) -> usize {
+        self.shared.callbacks.register(callback)
+    }
+
+    fn invoke_callbacks(&self, value: &T) {
+        self.shared.callbacks.invoke(value);
     }
 
     pub fn set_modified(&self) {
         self.modify(|_| true);
     }
+
+    #[must_use]
+    pub const fn try_lock_write(&self) -> Option<WriteGuard<'_, T>> {
+        // Writing never blocks for an unbounded period of time.
+        Some(WriteGuard { publisher: self })
+    }
+
+    pub fn write_sequence_deduplicated<I>(&self, items: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+        T: PartialEq,
+    {
+        let mut notify_count = 0;
+        for item in items {
+            if self.send_if_different(item) {
+                notify_count += 1;
+            }
+        }
+        notify_count
+    }
+
+    pub fn send_if_different(&self, new_value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.modify(|value| {
+            if *value == new_value {
+                return false;
+            }
+            *value = new_value;
+            true
+        })
+    }
+
+    pub fn write_cycle(&self, values: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        let index = self.shared.cycle_counter.fetch_add(1, Ordering::Relaxed) % values.len();
+        self.write(values[index].clone());
+        index
+    }
+
+    pub fn write_batch_last<I>(&self, items: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        // Consume the iterator before acquiring the write lock.
+        if let Some(last) = items.into_iter().last() {
+            self.write(last);
+        }
+    }
+
+    /// Address of the shared channel state.
+    ///
+    /// Identifies the channel and remains stable during its lifetime.
+    /// Obtaining the address does not require any locking.
+    fn channel_addr(&self) -> usize {
+        Arc::as_ptr(&self.shared).cast::<()>() as usize
+    }
+}
+
+#[cfg(feature = "tokio-rt")]
+impl<T> Publisher<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Forward all changes into a bounded channel.
+    ///
+    /// Spawns a task that sends a clone of the current value and of every
+    /// subsequent changed value into the returned receiver. Changes that
+    /// occur while the channel is full are coalesced into a single item.
+    ///
+    /// The task finishes after either the publisher or the receiver has
+    /// been dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not called from within a Tokio runtime or if `capacity` is 0.
+    #[must_use]
+    pub fn drain_subscribers_to_mpsc(&self, capacity: usize) -> tokio::sync::mpsc::Receiver<T> {
+        let (tx, rx) = tokio::sync::mpsc::channel(capacity);
+        let subscriber = self.subscribe_changed();
+        let initial_value = T::clone(&subscriber.read());
+        tokio::spawn(crate::tasklet::capture_into_mpsc(
+            subscriber,
+            initial_value,
+            |value, next_value| {
+                value.clone_from(next_value);
+                true
+            },
+            tx,
+        ));
+        rx
+    }
+}
+
+impl<T, E> Publisher<Result<T, E>> {
+    #[must_use]
+    pub fn map_err_published<F, E2>(self, map_err: F) -> Publisher<Result<T, E2>>
+    where
+        F: Fn(E) -> E2 + Send + Sync + 'static,
+        T: Clone + Send + Sync + 'static,
+        E: Clone + 'static,
+        E2: Send + Sync + 'static,
+    {
+        let map_value = move |value: &Result<T, E>| value.clone().map_err(&map_err);
+        // Register the callback while holding the read lock.
+        // Otherwise concurrent writes might be missed.
+        let value = self.read();
+        let initial_value = map_value(&value);
+        // The callback only holds a weak reference to the derived channel
+        // and is unregistered after all derived publishers have been dropped.
+        let shared = Arc::new_cyclic(|derived: &Weak<Shared<Result<T, E2>>>| {
+            let derived = Weak::clone(derived);
+            let callback_handle = self.on_change_callback(move |value| {
+                if let Some(derived) = derived.upgrade() {
+                    derived.write(map_value(value));
+                }
+            });
+            Shared::new(initial_value, Some(callback_handle))
+        });
+        drop(value);
+        Publisher { shared }
+    }
+}
+
+#[must_use]
+pub fn read_consistent_pair<'a, A, B>(
+    pa: &'a Publisher<A>,
+    pb: &'a Publisher<B>,
+) -> (Ref<'a, A>, Ref<'a, B>) {
+    // Acquire the locks ordered by the address of the shared values.
+    if pa.channel_addr() <= pb.channel_addr() {
+        let ra = pa.read();
+        let rb = pb.read();
+        (ra, rb)
+    } else {
+        let rb = pb.read();
+        let ra = pa.read();
+        (ra, rb)
+    }
+}
+
+pub fn swap_publishers<T>(a: &Publisher<T>, b: &Publisher<T>) {
+    if Arc::ptr_eq(&a.shared, &b.shared) {
+        return;
+    }
+    // Acquire the locks in the same order as read_consistent_pair().
+    let (outer, inner) = if a.channel_addr() <= b.channel_addr() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    outer.modify(|outer_value| {
+        inner.modify(|inner_value| {
+            std::mem::swap(outer_value, inner_value);
+            true
+        });
+        true
+    });
+}
+
+pub fn notify_batch<T>(publishers: &[&Publisher<T>]) {
+    // No amortization possible, every channel has its own lock.
+    for publisher in publishers {
+        publisher.set_modified();
+    }
 }
 
 impl<T> Clone for Publisher<T> {
     fn clone(&self) -> Self {
         Self {
-            tx: self.tx.clone(),
+            shared: Arc::clone(&self.shared),
         }
     }
 }
@@ -111,15 +614,39 @@ where
     }
 }
 
+#[derive(Debug)]
+pub struct WriteGuard<'a, T> {
+    publisher: &'a Publisher<T>,
+}
+
+impl<T> WriteGuard<'_, T> {
+    pub fn write(&self, new_value: T) {
+        self.publisher.write(new_value);
+    }
+
+    #[must_use]
+    pub fn replace(&self, new_value: T) -> T {
+        self.publisher.replace(new_value)
+    }
+
+    pub fn modify<M>(&self, modify: M) -> bool
+    where
+        M: FnOnce(&mut T) -> bool,
+    {
+        self.publisher.modify(modify)
+    }
+}
+
 #[derive(Debug)]
 pub struct Observer<T> {
     tx: watch::Sender<T>,
+    saturations: Arc<Saturations>,
 }
 
 impl<T> Observer<T> {
     #[must_use]
     pub fn subscribe(&self) -> Subscriber<T> {
-        Subscriber::new(self.tx.subscribe())
+        Subscriber::new(self.tx.subscribe(), &self.saturations)
     }
 
     #[must_use]
@@ -139,6 +666,17 @@ impl<T> Clone for Observer<T> {
     fn clone(&self) -> Self {
         Self {
             tx: self.tx.clone(),
+            saturations: Arc::clone(&self.saturations),
+        }
+    }
+}
+
+impl<T> From<watch::Sender<T>> for Observer<T> {
+    fn from(tx: watch::Sender<T>) -> Self {
+        // Saturation of subscribers is not visible to any publisher.
+        Self {
+            tx,
+            saturations: Arc::default(),
         }
     }
 }
@@ -146,11 +684,15 @@ impl<T> Clone for Observer<T> {
 #[derive(Debug)]
 pub struct Subscriber<T> {
     rx: watch::Receiver<T>,
+    saturated: Arc<AtomicBool>,
 }
 
 impl<T> Subscriber<T> {
-    const fn new(rx: watch::Receiver<T>) -> Self {
-        Self { rx }
+    fn new(rx: watch::Receiver<T>, saturations: &Saturations) -> Self {
+        Self {
+            rx,
+            saturated: saturations.register(),
+        }
     }
 
     #[must_use]
@@ -167,6 +709,19 @@ impl<T> Subscriber<T> {
         self.rx.mark_changed();
     }
 
+    pub fn signal_saturation(&self) {
+        self.saturated.store(true, Ordering::Relaxed);
+    }
+
+    pub fn clear_saturation(&self) {
+        self.saturated.store(false, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.rx.has_changed().unwrap_or(false)
+    }
+
     #[allow(clippy::missing_errors_doc)]
     pub async fn changed(&mut self) -> Result<(), OrphanedSubscriberError> {
         self.rx.changed().await.map_err(|_| OrphanedSubscriberError)
@@ -189,19 +744,35 @@ impl<T> Subscriber<T> {
     ) -> Result<U, OrphanedSubscriberError> {
         filter_map_changed(self, filter_map_fn).await
     }
+
+    pub async fn read_changed_into<U>(&mut self) -> Result<U, OrphanedSubscriberError>
+    where
+        U: for<'a> From<&'a T>,
+    {
+        self.map_changed(|next| U::from(next)).await
+    }
 }
 
 // Implementing Clone manually is required because #[derive(Clone)] would
 // require T to be Clone, too.
+// The clone inherits the changed state from the original.
 impl<T> Clone for Subscriber<T> {
     fn clone(&self) -> Self {
-        let Self { rx } = self;
-        Self { rx: rx.clone() }
+        let Self { rx, saturated } = self;
+        Self {
+            rx: rx.clone(),
+            saturated: Arc::clone(saturated),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc, Mutex,
+    };
+
     use crate::Publisher;
 
     // This test won't terminate if the value is not considered as changed as expected.
@@ -228,6 +799,216 @@ mod tests {
                 .is_err()
         );
     }
+
+    #[tokio::test]
+    async fn clone_unchanged_after_acknowledging() {
+        let tx = Publisher::new(0);
+        let mut rx = tx.subscribe_changed();
+        assert_eq!(0, *rx.read_ack());
+        let mut rx_clone = rx.clone();
+        assert!(!rx_clone.is_ready());
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(1), rx_clone.changed())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn clone_changed_before_acknowledging() {
+        let tx = Publisher::new(0);
+        let rx = tx.subscribe_changed();
+        let mut rx_clone = rx.clone();
+        assert!(rx_clone.is_ready());
+        assert_eq!(0, *rx_clone.read_changed().await.unwrap());
+        // The original is not affected by acknowledging the clone.
+        assert!(rx.is_ready());
+    }
+
+    #[test]
+    fn invoke_callback_until_handle_dropped() {
+        let tx = Publisher::new(0);
+        let last_value = Arc::new(AtomicI32::new(0));
+        let handle = tx.on_change_callback({
+            let last_value = Arc::clone(&last_value);
+            move |value| last_value.store(*value, Ordering::Relaxed)
+        });
+        tx.write(1);
+        assert_eq!(1, last_value.load(Ordering::Relaxed));
+        assert!(!tx.modify(|value| {
+            *value = 2;
+            false
+        }));
+        assert_eq!(1, last_value.load(Ordering::Relaxed));
+        assert_eq!(2, tx.clone().replace(3));
+        assert_eq!(3, last_value.load(Ordering::Relaxed));
+        drop(handle);
+        tx.write(4);
+        assert_eq!(3, last_value.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn drop_callback_handle_from_within_callback() {
+        let tx = Publisher::new(0);
+        let handle = Arc::new(Mutex::new(None));
+        *handle.lock().unwrap() = Some(tx.on_change_callback({
+            let handle = Arc::clone(&handle);
+            move |_| drop(handle.lock().unwrap().take())
+        }));
+        tx.write(1);
+        assert!(handle.lock().unwrap().is_none());
+        tx.write(2);
+    }
+
+    #[cfg(feature = "tokio-rt")]
+    #[tokio::test]
+    async fn drain_subscribers_to_mpsc() {
+        let tx = Publisher::new(0);
+        let mut rx = tx.drain_subscribers_to_mpsc(1);
+        assert_eq!(Some(0), rx.recv().await);
+        tx.write(1);
+        assert_eq!(Some(1), rx.recv().await);
+        drop(tx);
+        assert_eq!(None, rx.recv().await);
+    }
+
+    #[cfg(feature = "tokio-rt")]
+    #[tokio::test]
+    async fn drain_subscribers_to_mpsc_until_receiver_dropped() {
+        let tx = Publisher::new(0);
+        let rx = tx.drain_subscribers_to_mpsc(1);
+        assert!(tx.has_subscribers());
+        drop(rx);
+        // The spawned task finishes without any further changes.
+        while tx.has_subscribers() {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn map_err_published() {
+        let tx = Publisher::<Result<i32, i32>>::new(Err(1));
+        let derived = tx.clone().map_err_published(|err| err.to_string());
+        assert_eq!(Err("1".to_owned()), *derived.read());
+        tx.write(Ok(2));
+        assert_eq!(Ok(2), *derived.read());
+        tx.write(Err(3));
+        assert_eq!(Err("3".to_owned()), *derived.read());
+        let mut rx = derived.subscribe();
+        drop(derived);
+        // Orphaned, even though the source publisher is still alive.
+        assert!(rx.changed().await.is_err());
+        tx.write(Ok(4));
+    }
+
+    #[test]
+    fn read_consistent_pair() {
+        let tx1 = Publisher::new(1);
+        let tx2 = Publisher::new("2");
+        let (r1, r2) = super::read_consistent_pair(&tx1, &tx2);
+        assert_eq!(1, *r1);
+        assert_eq!("2", *r2);
+        drop((r1, r2));
+        // Independent of the order of the arguments.
+        let (r2, r1) = super::read_consistent_pair(&tx2, &tx1);
+        assert_eq!("2", *r2);
+        assert_eq!(1, *r1);
+    }
+
+    #[test]
+    fn swap_publishers() {
+        let tx1 = Publisher::new(1);
+        let tx2 = Publisher::new(2);
+        let mut rx1 = tx1.subscribe();
+        let mut rx2 = tx2.subscribe();
+        super::swap_publishers(&tx2, &tx1);
+        assert!(rx1.is_ready());
+        assert!(rx2.is_ready());
+        assert_eq!(2, *rx1.read_ack());
+        assert_eq!(1, *rx2.read_ack());
+        super::swap_publishers(&tx1, &tx1.clone());
+        assert!(!rx1.is_ready());
+    }
+
+    #[test]
+    fn atomic_update_retries_after_concurrent_write() {
+        let tx = Publisher::new(0);
+        let calls = std::cell::Cell::new(0);
+        tx.atomic_update(|value| {
+            calls.set(calls.get() + 1);
+            if calls.get() == 1 {
+                // Concurrent write that invalidates the first update.
+                tx.write(10);
+            }
+            value + 1
+        });
+        assert_eq!(2, calls.get());
+        assert_eq!(11, *tx.read());
+    }
+
+    #[test]
+    fn atomic_update_without_change() {
+        let tx = Publisher::new(0);
+        let rx = tx.subscribe();
+        tx.atomic_update(|value| *value);
+        assert!(!rx.is_ready());
+        tx.atomic_update(|value| value + 1);
+        assert!(rx.is_ready());
+    }
+
+    #[test]
+    fn error_channel_shared_by_clones() {
+        let tx = Publisher::new(0);
+        let errors = tx.error_channel();
+        assert!(errors.read().is_none());
+        assert!(errors.tx.same_channel(&tx.error_channel().tx));
+        assert!(errors.tx.same_channel(&tx.clone().error_channel().tx));
+        assert!(!errors
+            .tx
+            .same_channel(&Publisher::new(0).error_channel().tx));
+    }
+
+    #[test]
+    fn write_if_unsaturated() {
+        let tx = Publisher::new(0);
+        let rx1 = tx.subscribe();
+        let rx2 = tx.observe().subscribe();
+        assert!(tx.write_if_unsaturated(1));
+        rx1.signal_saturation();
+        assert!(!tx.write_if_unsaturated(2));
+        rx2.signal_saturation();
+        rx1.clear_saturation();
+        assert!(!tx.write_if_unsaturated(3));
+        // Dropped subscribers are no longer saturated.
+        drop(rx2);
+        assert!(tx.write_if_unsaturated(4));
+        rx1.signal_saturation();
+        drop(rx1);
+        let _rx3 = tx.subscribe();
+        assert!(tx.write_if_unsaturated(5));
+        assert_eq!(5, *tx.read());
+    }
+
+    #[test]
+    fn write_cycle_shared_by_clones() {
+        let tx = Publisher::new(0);
+        let values = [1, 2, 3];
+        assert_eq!(0, tx.write_cycle(&values));
+        assert_eq!(1, tx.clone().write_cycle(&values));
+        assert_eq!(2, tx.write_cycle(&values));
+        assert_eq!(3, *tx.read());
+    }
+
+    #[tokio::test]
+    async fn write_close_value_when_dropped() {
+        let tx = Publisher::new(0).with_close_value(-1);
+        let mut rx = tx.subscribe();
+        tx.write(1);
+        assert_eq!(1, *rx.read_changed().await.unwrap());
+        drop(tx);
+        assert_eq!(-1, *rx.read_changed().await.unwrap());
+        assert!(rx.read_changed().await.is_err());
+    }
 }
 
 #[cfg(test)]
@@ -332,6 +1113,10 @@ mod traits {
             self.mark_changed();
         }
 
+        fn is_ready(&self) -> bool {
+            self.is_ready()
+        }
+
         async fn changed(&mut self) -> Result<(), OrphanedSubscriberError> {
             self.changed().await
         }
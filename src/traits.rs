@@ -96,5 +96,6 @@ where
 
 pub(crate) trait ChangeListener {
     fn mark_changed(&mut self);
+    fn is_ready(&self) -> bool;
     async fn changed(&mut self) -> Result<(), OrphanedSubscriberError>;
 }
@@ -0,0 +1,138 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Undo and redo changes of a shared value.
+
+use std::{collections::VecDeque, ops::Deref};
+
+use crate::{Observer, Publisher, Subscriber};
+
+impl<T> Publisher<T> {
+    /// Track all writes for undo and redo.
+    ///
+    /// See also: [`UndoPublisher`]
+    #[must_use]
+    pub const fn with_undo(self) -> UndoPublisher<T> {
+        UndoPublisher::new(self)
+    }
+}
+
+/// Publisher with an undo/redo history.
+///
+/// Every write through this publisher records the previous value
+/// in the history. Changes can then be undone and redone. Each of
+/// these operations emits a change notification.
+///
+/// Writes through other instances of the wrapped publisher are not
+/// recorded in the history.
+///
+/// The history is unbounded and grows with every write. Use
+/// [`into_inner()`](Self::into_inner) to discard it.
+#[allow(missing_debug_implementations)]
+pub struct UndoPublisher<T> {
+    publisher: Publisher<T>,
+    history: VecDeque<T>,
+    future: VecDeque<T>,
+}
+
+impl<T> UndoPublisher<T> {
+    /// Wrap a publisher with an empty history.
+    #[must_use]
+    pub const fn new(publisher: Publisher<T>) -> Self {
+        Self {
+            publisher,
+            history: VecDeque::new(),
+            future: VecDeque::new(),
+        }
+    }
+
+    /// Create an [`Observer`] for read-only access.
+    #[must_use]
+    pub fn observe(&self) -> Observer<T> {
+        self.publisher.observe()
+    }
+
+    /// Subscribe to changes.
+    #[must_use]
+    pub fn subscribe(&self) -> Subscriber<T> {
+        self.publisher.subscribe()
+    }
+
+    /// Obtain a reference to the current value.
+    ///
+    /// See also: [`Publisher::read()`]
+    #[must_use]
+    pub fn read(&self) -> impl Deref<Target = T> + '_ {
+        self.publisher.read()
+    }
+
+    /// Overwrite the current value with a new value
+    /// and emit a change notification.
+    ///
+    /// The previous value is recorded in the history. All undone
+    /// changes are discarded and could no longer be redone.
+    pub fn write(&mut self, new_value: T) {
+        let old_value = self.publisher.replace(new_value);
+        self.history.push_back(old_value);
+        self.future.clear();
+    }
+
+    /// Revert the last write.
+    ///
+    /// Returns `true` if a change has been undone or `false`
+    /// if the history is empty.
+    pub fn undo(&mut self) -> bool {
+        let Some(old_value) = self.history.pop_back() else {
+            return false;
+        };
+        let undone_value = self.publisher.replace(old_value);
+        self.future.push_back(undone_value);
+        true
+    }
+
+    /// Restore the last undone write.
+    ///
+    /// Returns `true` if a change has been redone or `false`
+    /// if no undone changes are available.
+    pub fn redo(&mut self) -> bool {
+        let Some(undone_value) = self.future.pop_back() else {
+            return false;
+        };
+        let old_value = self.publisher.replace(undone_value);
+        self.history.push_back(old_value);
+        true
+    }
+
+    /// Unwrap the publisher and discard the history.
+    #[must_use]
+    pub fn into_inner(self) -> Publisher<T> {
+        let Self { publisher, .. } = self;
+        publisher
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use crate::Publisher;
+
+    #[test]
+    fn undo_redo() {
+        let mut tx = Publisher::new(0).with_undo();
+        let rx = tx.observe();
+        assert!(!tx.undo());
+        tx.write(1);
+        tx.write(2);
+        assert!(tx.undo());
+        assert_eq!(1, *rx.read());
+        assert!(tx.undo());
+        assert_eq!(0, *rx.read());
+        assert!(!tx.undo());
+        assert!(tx.redo());
+        assert_eq!(1, *rx.read());
+        tx.write(3);
+        assert!(!tx.redo());
+        assert!(tx.undo());
+        assert_eq!(1, *rx.read());
+        assert_eq!(1, *tx.read());
+    }
+}
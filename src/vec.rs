@@ -0,0 +1,45 @@
+// SPDX-FileCopyrightText: The discro authors
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::Publisher;
+
+/// Extension methods for publishers of [`Vec`] values.
+pub trait VecPublisherExt {
+    /// Shorten the vector to `len` elements.
+    ///
+    /// Emits a change notification only if elements have been removed,
+    /// i.e. if the vector has been longer than `len`.
+    ///
+    /// Returns `true` if a change notification has been emitted.
+    fn truncate(&self, len: usize) -> bool;
+}
+
+impl<T> VecPublisherExt for Publisher<Vec<T>> {
+    fn truncate(&self, len: usize) -> bool {
+        self.modify(|vec| {
+            if vec.len() <= len {
+                return false;
+            }
+            vec.truncate(len);
+            true
+        })
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use super::VecPublisherExt as _;
+    use crate::Publisher;
+
+    #[test]
+    fn truncate() {
+        let tx = Publisher::new(vec![1, 2, 3]);
+        let mut rx = tx.subscribe();
+        assert!(!tx.truncate(3));
+        assert!(!tx.truncate(4));
+        assert!(!rx.is_ready());
+        assert!(tx.truncate(1));
+        assert!(rx.is_ready());
+        assert_eq!(vec![1], *rx.read_ack());
+    }
+}